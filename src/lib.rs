@@ -0,0 +1,61 @@
+pub mod rng;
+pub mod search;
+pub mod year2016;
+
+pub struct Puzzle {
+    pub day: u8,
+    /// Returns the answer to each part, in order.
+    pub solve: fn(&str) -> Vec<String>,
+    pub input: &'static str,
+    /// Known answers for `input`, in part order. Parts without one, or all of
+    /// them when the list is empty, are not checked.
+    pub answers: &'static [&'static str],
+}
+
+/// How an answer compares with the one recorded for the puzzle.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Check {
+    Unknown,
+    Correct,
+    Wrong { expected: &'static str },
+}
+
+impl Puzzle {
+    pub fn check(&self, part: usize, answer: &str) -> Check {
+        match self.answers.get(part) {
+            None => Check::Unknown,
+            Some(&expected) if expected == answer => Check::Correct,
+            Some(&expected) => Check::Wrong { expected },
+        }
+    }
+}
+
+pub fn puzzles(year: u16) -> Option<&'static [Puzzle]> {
+    match year {
+        2016 => Some(year2016::PUZZLES),
+        _ => None,
+    }
+}
+
+pub fn find_puzzle(year: u16, day: u8) -> Option<&'static Puzzle> {
+    puzzles(year)?.iter().find(|p| p.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let puzzle = Puzzle {
+            day: 1,
+            solve: |_| vec![],
+            input: "",
+            answers: &["12"],
+        };
+
+        assert_eq!(puzzle.check(0, "12"), Check::Correct);
+        assert_eq!(puzzle.check(0, "13"), Check::Wrong { expected: "12" });
+        assert_eq!(puzzle.check(1, "4"), Check::Unknown);
+    }
+}
@@ -1,34 +1,345 @@
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
+use std::{
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Write},
+    path::PathBuf,
+    process, thread,
+    time::{Duration, Instant},
+};
 
-use clap::Parser;
+use advent_of_code::{
+    find_puzzle,
+    year2016::{day1, day2, day3, day4},
+    Check,
+};
+use clap::{Parser, Subcommand, ValueEnum};
 
-/// Simple program to greet a person
+/// Advent of Code solutions
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    /// Number of times to greet
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Puzzle year
+    #[arg(short, long, default_value_t = 2016)]
+    year: u16,
+
+    /// Puzzle day
     #[arg(short, long, default_value_t = 1)]
     day: u8,
+
+    /// Draw the puzzle instead of solving it (2016 day 1 only)
+    #[arg(long, value_enum)]
+    visualize: Option<Visualization>,
+
+    /// File with a keypad drawing to use instead of the puzzle's (2016 day 2 only)
+    #[arg(long)]
+    keypad: Option<PathBuf>,
+
+    /// Label of the key to start from on a custom keypad
+    #[arg(long, default_value_t = day2::DEFAULT_START, requires = "keypad")]
+    start: char,
+
+    /// Replay the moves one at a time on the keypad (2016 day 2 only)
+    #[arg(long)]
+    animate: bool,
+
+    /// Milliseconds between the frames of --animate
+    #[arg(long, default_value_t = 200, requires = "animate")]
+    delay: u64,
+
+    /// Count the kinds of triangles instead of solving, in the puzzle input
+    /// or the --triangles listing (2016 day 3 only)
+    #[arg(long, conflicts_with = "invalid")]
+    report: bool,
+
+    /// Count the triangles in a listing file of any size, reading it line
+    /// by line, or read it for --report and --invalid (2016 day 3 only)
+    #[arg(long)]
+    triangles: Option<PathBuf>,
+
+    /// List the impossible triangles, reading sides along rows or down
+    /// columns (2016 day 3 only)
+    #[arg(long, value_enum)]
+    invalid: Option<Reading>,
+
+    /// Output format of --invalid
+    #[arg(long, value_enum, default_value_t = Format::Text, requires = "invalid")]
+    format: Format,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Search the decrypted names of the real rooms from 2016 day 4, or
+    /// list them all by sector
+    Rooms {
+        /// Text to look for in the names; without it every room is listed
+        pattern: Option<String>,
+
+        /// Match the whole name against a pattern with `*` and `?`
+        #[arg(long, requires = "pattern", conflicts_with = "regex")]
+        glob: bool,
+
+        /// Treat the pattern as a regular expression
+        #[arg(long, requires = "pattern")]
+        regex: bool,
+
+        /// File with room listings to use instead of the puzzle's
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Visualization {
+    Ascii,
+    Svg,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Reading {
+    Rows,
+    Columns,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Format {
+    Text,
+    Csv,
 }
 
 fn main() {
     let args = Args::parse();
-    println!("Solving Day {}", args.day);
-
-    match args.day {
-        1 => day1::solve(include_str!("data/day1.txt")),
-        2 => day2::solve(include_str!("data/day2.txt")),
-        3 => day3::solve(include_str!("data/day3.txt")),
-        4 => day4::solve(include_str!("data/day4.txt")),
-        5 => day5::solve(include_str!("data/day5.txt")),
-        6 => day6::solve(include_str!("data/day6.txt")),
-        7 => day7::solve(include_str!("data/day7.txt")),
-        _ => todo!(),
+
+    if let Some(command) = &args.command {
+        run(command);
+        return;
+    }
+    let Some(puzzle) = find_puzzle(args.year, args.day) else {
+        eprintln!("No solution for {} day {}", args.year, args.day);
+        process::exit(1);
+    };
+
+    if let Some(visualization) = args.visualize {
+        only_for(&args, (2016, 1), "--visualize");
+
+        let instructions = day1::parse(puzzle.input).unwrap_or_else(|e| {
+            eprintln!("Invalid input: {}", e);
+            process::exit(1);
+        });
+        let pointer = day1::Pointer::walk(&instructions);
+
+        match visualization {
+            Visualization::Ascii => print!("{}", day1::render::ascii(&pointer, 80, 40)),
+            Visualization::Svg => print!("{}", day1::render::svg(&pointer)),
+        }
+
+        return;
+    }
+
+    if args.animate {
+        only_for(&args, (2016, 2), "--animate");
+
+        let lines = day2::parse(puzzle.input);
+        animate(keyboard(&args), &lines, Duration::from_millis(args.delay));
+        return;
+    }
+
+    if args.keypad.is_some() {
+        only_for(&args, (2016, 2), "--keypad");
+
+        println!("Code: {}", keyboard(&args).code(&day2::parse(puzzle.input)));
+        return;
+    }
+
+    if let Some(reading) = args.invalid {
+        only_for(&args, (2016, 3), "--invalid");
+
+        let reading = match reading {
+            Reading::Rows => day3::Reading::Rows,
+            Reading::Columns => day3::Reading::Columns,
+        };
+
+        let invalid = day3::invalid_triangles(listing(&args, puzzle.input), reading)
+            .unwrap_or_else(|e| {
+                eprintln!("Invalid listing: {}", e);
+                process::exit(1);
+            });
+
+        match args.format {
+            Format::Text => invalid.iter().for_each(|triangle| println!("{}", triangle)),
+            Format::Csv => print!("{}", day3::to_csv(&invalid)),
+        }
+        return;
+    }
+
+    if args.report {
+        only_for(&args, (2016, 3), "--report");
+
+        let (by_rows, by_columns) =
+            day3::report(listing(&args, puzzle.input)).unwrap_or_else(|e| {
+                eprintln!("Invalid listing: {}", e);
+                process::exit(1);
+            });
+        println!("By rows\n{}\nBy columns\n{}", by_rows, by_columns);
+        return;
+    }
+
+    if args.triangles.is_some() {
+        only_for(&args, (2016, 3), "--triangles");
+
+        match day3::count(listing(&args, puzzle.input)) {
+            Ok(counts) => println!(
+                "Part One: {}\nPart Two: {}",
+                counts.by_rows, counts.by_columns
+            ),
+            Err(e) => {
+                eprintln!("Invalid listing: {}", e);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    println!("Solving {} Day {}", args.year, args.day);
+
+    let start = Instant::now();
+    let answers = (puzzle.solve)(puzzle.input);
+    let elapsed = start.elapsed();
+
+    let mut wrong = false;
+    for (part, answer) in answers.iter().enumerate() {
+        let name = match part {
+            0 => "Part One".to_string(),
+            1 => "Part Two".to_string(),
+            _ => format!("Part {}", part + 1),
+        };
+
+        match puzzle.check(part, answer) {
+            Check::Unknown => println!("{}: {}", name, answer),
+            Check::Correct => println!("{}: {} (correct)", name, answer),
+            Check::Wrong { expected } => {
+                println!("{}: {} (expected {})", name, answer, expected);
+                wrong = true;
+            }
+        }
+    }
+
+    println!("Solved in {:.2?}", elapsed);
+
+    if wrong {
+        process::exit(1);
+    }
+}
+
+fn run(command: &Command) {
+    let Command::Rooms {
+        pattern,
+        glob,
+        regex,
+        file,
+    } = command;
+
+    let input = match file {
+        Some(path) => fs::read_to_string(path).unwrap_or_else(|e| {
+            eprintln!("Cannot read {}: {}", path.display(), e);
+            process::exit(1);
+        }),
+        None => find_puzzle(2016, 4).unwrap().input.to_string(),
+    };
+
+    let rooms = match pattern {
+        None => day4::directory(&input),
+        Some(pattern) if *glob => day4::search(&input, &day4::Query::glob(pattern)),
+        Some(pattern) if *regex => {
+            let query = day4::Query::regex(pattern).unwrap_or_else(|e| {
+                eprintln!("Invalid regular expression: {}", e);
+                process::exit(1);
+            });
+            day4::search(&input, &query)
+        }
+        Some(pattern) => day4::search(&input, &day4::Query::Substring(pattern.clone())),
+    }
+    .unwrap_or_else(|e| {
+        eprintln!("Invalid listing: {}", e);
+        process::exit(1);
+    });
+
+    for room in rooms {
+        println!("{:>4}  {}", room.sector, room.name);
+    }
+}
+
+// The keypad from --keypad, or the square one from part one.
+fn keyboard(args: &Args) -> day2::Keyboard {
+    let Some(path) = &args.keypad else {
+        return day2::Keyboard::from_layout(day2::SQUARE, day2::DEFAULT_START).unwrap();
+    };
+
+    let layout = fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("Cannot read {}: {}", path.display(), e);
+        process::exit(1);
+    });
+
+    day2::Keyboard::from_layout(&layout, args.start).unwrap_or_else(|e| {
+        eprintln!("Invalid keypad in {}: {}", path.display(), e);
+        process::exit(1);
+    })
+}
+
+// The listing from --triangles, or the puzzle input.
+fn listing<'a>(args: &Args, input: &'a str) -> Box<dyn BufRead + 'a> {
+    let Some(path) = &args.triangles else {
+        return Box::new(input.as_bytes());
+    };
+
+    let file = File::open(path).unwrap_or_else(|e| {
+        eprintln!("Cannot read {}: {}", path.display(), e);
+        process::exit(1);
+    });
+
+    Box::new(BufReader::new(file))
+}
+
+fn animate(mut keyboard: day2::Keyboard, lines: &[Vec<day2::Instruction>], delay: Duration) {
+    let trace = keyboard.trace(lines);
+    let mut code = String::new();
+
+    for (n, line) in trace.iter().enumerate() {
+        for (i, step) in line.iter().enumerate() {
+            // Clear the terminal and move the cursor home before each frame.
+            print!("\x1b[2J\x1b[H{}", keyboard.draw(step.to));
+            println!(
+                "Line {}/{}, move {}/{}: {:?} from {} to {}{}",
+                n + 1,
+                trace.len(),
+                i + 1,
+                line.len(),
+                step.instruction,
+                step.from,
+                step.to,
+                if step.blocked { " (blocked)" } else { "" }
+            );
+            println!("Code: {}", code);
+
+            io::stdout().flush().unwrap();
+            thread::sleep(delay);
+        }
+
+        if let Some(step) = line.last() {
+            code.push(step.to);
+        }
+    }
+
+    println!("Code: {}", code);
+}
+
+fn only_for(args: &Args, puzzle: (u16, u8), option: &str) {
+    if (args.year, args.day) != puzzle {
+        eprintln!(
+            "{} is only available for {} day {}",
+            option, puzzle.0, puzzle.1
+        );
+        process::exit(1);
     }
 }
@@ -0,0 +1,42 @@
+/// A linear congruential generator, for test data and decoys that should
+/// look random but come out the same for the same seed.
+pub struct Lcg(u64);
+
+impl Lcg {
+    pub fn new(seed: u64) -> Self {
+        Lcg(seed)
+    }
+
+    /// A number below `n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+
+        // The low bits of an LCG repeat quickly, so use the high ones.
+        (self.0 >> 33) as usize % n
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_below() {
+        let draws = |seed| {
+            let mut rng = Lcg::new(seed);
+            (0..100).map(|_| rng.below(6)).collect::<Vec<_>>()
+        };
+
+        assert_eq!(draws(1), draws(1));
+        assert_ne!(draws(1), draws(2));
+        assert!(draws(1).iter().all(|&n| n < 6));
+        assert!((0..6).all(|n| draws(1).contains(&n)));
+    }
+}
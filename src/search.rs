@@ -0,0 +1,374 @@
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+pub trait State: Clone {
+    type Key: Hash + Eq;
+
+    /// Neighbouring states together with the cost of moving to each of them.
+    fn successors(&self) -> Vec<(Self, u32)>;
+
+    /// A lower bound on the cost still needed to reach a goal. Only A* uses it.
+    fn heuristic(&self) -> u32 {
+        0
+    }
+
+    /// Two states with the same key are treated as the same node, so
+    /// symmetric states can share one key to shrink the search.
+    fn key(&self) -> Self::Key;
+}
+
+#[derive(Debug)]
+pub struct Solution<S> {
+    /// Every state from the start to the goal, both included.
+    pub path: Vec<S>,
+    pub cost: u32,
+}
+
+#[derive(Debug)]
+pub struct Outcome<S> {
+    pub solution: Option<Solution<S>>,
+    /// Number of states whose successors were expanded.
+    pub visited: usize,
+}
+
+struct Node<S> {
+    state: S,
+    parent: Option<usize>,
+    cost: u32,
+}
+
+fn path_to<S: Clone>(nodes: &[Node<S>], mut index: usize) -> Solution<S> {
+    let cost = nodes[index].cost;
+    let mut path = vec![nodes[index].state.clone()];
+
+    while let Some(parent) = nodes[index].parent {
+        path.push(nodes[parent].state.clone());
+        index = parent;
+    }
+
+    path.reverse();
+    Solution { path, cost }
+}
+
+/// Fewest moves, ignoring the cost of each move.
+pub fn bfs<S: State>(start: S, is_goal: impl Fn(&S) -> bool) -> Outcome<S> {
+    let mut seen = HashSet::from([start.key()]);
+    let mut nodes = vec![Node {
+        state: start,
+        parent: None,
+        cost: 0,
+    }];
+    let mut queue = VecDeque::from([0]);
+    let mut visited = 0;
+
+    while let Some(index) = queue.pop_front() {
+        if is_goal(&nodes[index].state) {
+            return Outcome {
+                solution: Some(path_to(&nodes, index)),
+                visited,
+            };
+        }
+
+        visited += 1;
+
+        for (next, step) in nodes[index].state.successors() {
+            if seen.insert(next.key()) {
+                queue.push_back(nodes.len());
+                nodes.push(Node {
+                    state: next,
+                    parent: Some(index),
+                    cost: nodes[index].cost + step,
+                });
+            }
+        }
+    }
+
+    Outcome {
+        solution: None,
+        visited,
+    }
+}
+
+/// Number of moves from `start` to every reachable state.
+pub fn bfs_distances<S: State>(start: S) -> HashMap<S::Key, u32> {
+    let mut distances = HashMap::from([(start.key(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((state, steps)) = queue.pop_front() {
+        for (next, _) in state.successors() {
+            if let Entry::Vacant(entry) = distances.entry(next.key()) {
+                entry.insert(steps + 1);
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+/// Cheapest path, using the sum of the successor costs.
+pub fn dijkstra<S: State>(start: S, is_goal: impl Fn(&S) -> bool) -> Outcome<S> {
+    best_first(start, is_goal, |_| 0)
+}
+
+/// Cheapest path, guided by `State::heuristic`. The heuristic must never
+/// overestimate for the result to be optimal.
+pub fn astar<S: State>(start: S, is_goal: impl Fn(&S) -> bool) -> Outcome<S> {
+    best_first(start, is_goal, S::heuristic)
+}
+
+fn best_first<S: State>(
+    start: S,
+    is_goal: impl Fn(&S) -> bool,
+    heuristic: impl Fn(&S) -> u32,
+) -> Outcome<S> {
+    // Maps each key to the node holding its cheapest known path. Queue
+    // entries pointing at any other node are stale and get skipped.
+    let mut best = HashMap::from([(start.key(), 0)]);
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), 0))]);
+    let mut nodes = vec![Node {
+        state: start,
+        parent: None,
+        cost: 0,
+    }];
+    let mut visited = 0;
+
+    while let Some(Reverse((_, index))) = queue.pop() {
+        let node = &nodes[index];
+        if best.get(&node.state.key()) != Some(&index) {
+            continue;
+        }
+
+        if is_goal(&node.state) {
+            return Outcome {
+                solution: Some(path_to(&nodes, index)),
+                visited,
+            };
+        }
+
+        visited += 1;
+        let cost = node.cost;
+
+        for (next, step) in node.state.successors() {
+            let next_cost = cost + step;
+            let key = next.key();
+
+            if best.get(&key).is_some_and(|&i| nodes[i].cost <= next_cost) {
+                continue;
+            }
+
+            best.insert(key, nodes.len());
+            queue.push(Reverse((next_cost + heuristic(&next), nodes.len())));
+            nodes.push(Node {
+                state: next,
+                parent: Some(index),
+                cost: next_cost,
+            });
+        }
+    }
+
+    Outcome {
+        solution: None,
+        visited,
+    }
+}
+
+/// Fewest moves like `bfs`, but with memory proportional to the path length.
+/// Gives up after `max_depth` moves.
+pub fn iddfs<S: State>(start: S, is_goal: impl Fn(&S) -> bool, max_depth: usize) -> Outcome<S> {
+    let mut visited = 0;
+
+    for limit in 0..=max_depth {
+        let mut path = vec![(start.clone(), 0)];
+        let mut on_path = HashSet::from([start.key()]);
+
+        if depth_limited(&mut path, &mut on_path, &is_goal, limit, &mut visited) {
+            return Outcome {
+                solution: Some(Solution {
+                    cost: path.iter().map(|(_, step)| step).sum(),
+                    path: path.into_iter().map(|(state, _)| state).collect(),
+                }),
+                visited,
+            };
+        }
+    }
+
+    Outcome {
+        solution: None,
+        visited,
+    }
+}
+
+fn depth_limited<S: State>(
+    path: &mut Vec<(S, u32)>,
+    on_path: &mut HashSet<S::Key>,
+    is_goal: &impl Fn(&S) -> bool,
+    limit: usize,
+    visited: &mut usize,
+) -> bool {
+    let (state, _) = path.last().unwrap();
+
+    if is_goal(state) {
+        return true;
+    }
+
+    if limit == 0 {
+        return false;
+    }
+
+    *visited += 1;
+
+    for (next, step) in state.successors() {
+        let key = next.key();
+        if on_path.contains(&key) {
+            continue;
+        }
+
+        path.push((next, step));
+        on_path.insert(key);
+
+        if depth_limited(path, on_path, is_goal, limit - 1, visited) {
+            return true;
+        }
+
+        let (next, _) = path.pop().unwrap();
+        on_path.remove(&next.key());
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Reach a number by adding one (costs 1) or doubling (costs 5).
+    #[derive(Clone, Debug, PartialEq)]
+    struct Number {
+        value: u32,
+        target: u32,
+    }
+
+    impl State for Number {
+        type Key = u32;
+
+        fn successors(&self) -> Vec<(Self, u32)> {
+            [(self.value + 1, 1), (self.value * 2, 5)]
+                .into_iter()
+                .filter(|&(value, _)| value <= self.target)
+                .map(|(value, cost)| (Number { value, ..*self }, cost))
+                .collect()
+        }
+
+        fn heuristic(&self) -> u32 {
+            // Either every remaining move adds one, or there is at least one doubling.
+            (self.target - self.value).min(5)
+        }
+
+        fn key(&self) -> u32 {
+            self.value
+        }
+    }
+
+    fn values(solution: &Solution<Number>) -> Vec<u32> {
+        solution.path.iter().map(|n| n.value).collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let outcome = bfs(
+            Number {
+                value: 1,
+                target: 12,
+            },
+            |n| n.value == 12,
+        );
+        let solution = outcome.solution.unwrap();
+
+        assert_eq!(values(&solution), vec![1, 2, 3, 6, 12]);
+        assert_eq!(solution.cost, 12);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let outcome = dijkstra(
+            Number {
+                value: 1,
+                target: 12,
+            },
+            |n| n.value == 12,
+        );
+        let solution = outcome.solution.unwrap();
+
+        assert_eq!(values(&solution), vec![1, 2, 3, 4, 5, 6, 12]);
+        assert_eq!(solution.cost, 10);
+    }
+
+    #[test]
+    fn test_astar() {
+        let start = Number {
+            value: 1,
+            target: 40,
+        };
+        let astar = astar(start.clone(), |n| n.value == 40);
+        let dijkstra = dijkstra(start, |n| n.value == 40);
+
+        assert_eq!(
+            astar.solution.unwrap().cost,
+            dijkstra.solution.unwrap().cost
+        );
+        assert!(astar.visited <= dijkstra.visited);
+    }
+
+    #[test]
+    fn test_iddfs() {
+        let outcome = iddfs(
+            Number {
+                value: 1,
+                target: 12,
+            },
+            |n| n.value == 12,
+            10,
+        );
+
+        assert_eq!(outcome.solution.unwrap().path.len(), 5);
+
+        let outcome = iddfs(
+            Number {
+                value: 1,
+                target: 12,
+            },
+            |n| n.value == 12,
+            3,
+        );
+        assert!(outcome.solution.is_none());
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let outcome = bfs(
+            Number {
+                value: 1,
+                target: 12,
+            },
+            |n| n.value == 13,
+        );
+
+        assert!(outcome.solution.is_none());
+        assert_eq!(outcome.visited, 12);
+    }
+
+    #[test]
+    fn test_bfs_distances() {
+        let distances = bfs_distances(Number {
+            value: 1,
+            target: 12,
+        });
+
+        assert_eq!(distances.len(), 12);
+        assert_eq!(distances[&12], 4);
+    }
+}
@@ -0,0 +1,466 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{i64, one_of},
+    combinator::map,
+    sequence::{preceded, separated_pair},
+    IResult,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Operand {
+    Register(usize),
+    Value(i64),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Instruction {
+    Cpy(Operand, Operand),
+    Inc(Operand),
+    Dec(Operand),
+    Jnz(Operand, Operand),
+    Tgl(Operand),
+    Out(Operand),
+}
+
+impl Instruction {
+    fn from(input: &str) -> IResult<&str, Instruction> {
+        alt((
+            map(
+                preceded(tag("cpy "), separated_pair(operand, tag(" "), operand)),
+                |(x, y)| Instruction::Cpy(x, y),
+            ),
+            map(preceded(tag("inc "), operand), Instruction::Inc),
+            map(preceded(tag("dec "), operand), Instruction::Dec),
+            map(
+                preceded(tag("jnz "), separated_pair(operand, tag(" "), operand)),
+                |(x, y)| Instruction::Jnz(x, y),
+            ),
+            map(preceded(tag("tgl "), operand), Instruction::Tgl),
+            map(preceded(tag("out "), operand), Instruction::Out),
+        ))(input)
+    }
+
+    fn toggle(self) -> Instruction {
+        match self {
+            Instruction::Inc(x) => Instruction::Dec(x),
+            Instruction::Dec(x) | Instruction::Tgl(x) | Instruction::Out(x) => Instruction::Inc(x),
+            Instruction::Jnz(x, y) => Instruction::Cpy(x, y),
+            Instruction::Cpy(x, y) => Instruction::Jnz(x, y),
+        }
+    }
+}
+
+fn operand(input: &str) -> IResult<&str, Operand> {
+    alt((
+        map(one_of("abcd"), |r| {
+            Operand::Register(r as usize - 'a' as usize)
+        }),
+        map(i64, Operand::Value),
+    ))(input)
+}
+
+pub fn parse_program(input: &str) -> Vec<Instruction> {
+    input
+        .lines()
+        .map(Instruction::from)
+        .map(Result::unwrap)
+        .map(|(_, i)| i)
+        .collect()
+}
+
+// A run of instructions that the machine can execute in one go. Macros are
+// only ever entered from their first instruction, so a jump into the middle
+// of one still executes the underlying instructions one by one.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Macro {
+    // inc target / dec counter / jnz counter -2
+    Add {
+        target: usize,
+        counter: usize,
+    },
+    // cpy source inner / inc target / dec inner / jnz inner -2 / dec outer / jnz outer -5
+    Multiply {
+        target: usize,
+        source: Operand,
+        inner: usize,
+        outer: usize,
+    },
+}
+
+impl Macro {
+    fn len(&self) -> usize {
+        match self {
+            Macro::Add { .. } => 3,
+            Macro::Multiply { .. } => 6,
+        }
+    }
+}
+
+fn find_add(window: &[Instruction]) -> Option<(usize, usize)> {
+    use Instruction::*;
+    use Operand::*;
+
+    match window {
+        [Inc(Register(t)), Dec(Register(c)), Jnz(Register(j), Value(-2)), ..]
+        | [Dec(Register(c)), Inc(Register(t)), Jnz(Register(j), Value(-2)), ..]
+            if t != c && c == j =>
+        {
+            Some((*t, *c))
+        }
+        _ => None,
+    }
+}
+
+fn find_macro(window: &[Instruction]) -> Option<Macro> {
+    use Instruction::*;
+    use Operand::*;
+
+    if let [Cpy(source, Register(inner)), rest @ ..] = window {
+        if let Some((target, counter)) = find_add(rest) {
+            if let [_, _, _, Dec(Register(outer)), Jnz(Register(j), Value(-5)), ..] = rest {
+                let distinct = counter == *inner
+                    && outer == j
+                    && target != *outer
+                    && inner != outer
+                    && !matches!(source, Register(s) if [target, *inner, *outer].contains(s));
+
+                if distinct {
+                    return Some(Macro::Multiply {
+                        target,
+                        source: *source,
+                        inner: *inner,
+                        outer: *outer,
+                    });
+                }
+            }
+        }
+    }
+
+    find_add(window).map(|(target, counter)| Macro::Add { target, counter })
+}
+
+pub struct Machine {
+    pub registers: [i64; 4],
+    program: Vec<Instruction>,
+    macros: Vec<Option<Macro>>,
+    optimise: bool,
+    pc: i64,
+}
+
+impl Machine {
+    pub fn new(program: Vec<Instruction>) -> Self {
+        let mut machine = Machine {
+            registers: [0; 4],
+            program,
+            macros: vec![],
+            optimise: true,
+            pc: 0,
+        };
+        machine.optimise_program();
+        machine
+    }
+
+    #[cfg(test)]
+    pub fn without_optimisation(mut self) -> Self {
+        self.optimise = false;
+        self.macros = vec![None; self.program.len()];
+        self
+    }
+
+    // Recomputed from scratch whenever `tgl` rewrites the program, which is
+    // cheap for programs of a few dozen lines and means a toggle landing
+    // inside an optimised region can never leave a stale macro behind.
+    fn optimise_program(&mut self) {
+        self.macros = (0..self.program.len())
+            .map(|i| {
+                if self.optimise {
+                    find_macro(&self.program[i..])
+                } else {
+                    None
+                }
+            })
+            .collect();
+    }
+
+    fn value(&self, operand: Operand) -> i64 {
+        match operand {
+            Operand::Register(r) => self.registers[r],
+            Operand::Value(v) => v,
+        }
+    }
+
+    fn current(&self) -> Option<usize> {
+        usize::try_from(self.pc)
+            .ok()
+            .filter(|&pc| pc < self.program.len())
+    }
+
+    pub fn run(&mut self) {
+        while self.current().is_some() {
+            self.step();
+        }
+    }
+
+    /// Runs until the next `out` instruction and returns its value. Gives up
+    /// when the program halts, or when a jump back brings the machine to a
+    /// state it was already in since the last output, since it would then
+    /// loop forever without printing anything.
+    pub fn next_output(&mut self) -> Output {
+        // Brent's cycle detection: compare against one saved state, saving a
+        // new one after twice as many jumps each time.
+        let mut saved = self.snapshot();
+        let mut limit = 1;
+        let mut jumps = 0;
+
+        while self.current().is_some() {
+            let pc = self.pc;
+
+            if let Some(output) = self.step() {
+                return Output::Value(output);
+            }
+
+            if self.pc > pc {
+                continue;
+            }
+
+            if self.pc == saved.pc
+                && self.registers == saved.registers
+                && self.program == saved.program
+            {
+                return Output::Stuck;
+            }
+
+            jumps += 1;
+            if jumps == limit {
+                saved = self.snapshot();
+                limit *= 2;
+                jumps = 0;
+            }
+        }
+
+        Output::Halted
+    }
+
+    /// Everything that determines how the machine continues from here,
+    /// including the program itself since `tgl` may have rewritten it.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            pc: self.pc,
+            registers: self.registers,
+            program: self.program.clone(),
+        }
+    }
+
+    fn step(&mut self) -> Option<i64> {
+        let pc = self.current()?;
+
+        if self.execute_macro(pc) {
+            None
+        } else {
+            self.execute(pc)
+        }
+    }
+
+    fn execute_macro(&mut self, pc: usize) -> bool {
+        let Some(m) = self.macros[pc] else {
+            return false;
+        };
+
+        match m {
+            Macro::Add { target, counter } => {
+                let count = self.registers[counter];
+                if count <= 0 {
+                    return false;
+                }
+
+                self.registers[target] += count;
+                self.registers[counter] = 0;
+            }
+            Macro::Multiply {
+                target,
+                source,
+                inner,
+                outer,
+            } => {
+                let (factor, count) = (self.value(source), self.registers[outer]);
+                if factor <= 0 || count <= 0 {
+                    return false;
+                }
+
+                self.registers[target] += factor * count;
+                self.registers[inner] = 0;
+                self.registers[outer] = 0;
+            }
+        }
+
+        self.pc += m.len() as i64;
+        true
+    }
+
+    fn execute(&mut self, pc: usize) -> Option<i64> {
+        let mut output = None;
+
+        match self.program[pc] {
+            Instruction::Cpy(x, Operand::Register(r)) => self.registers[r] = self.value(x),
+            Instruction::Inc(Operand::Register(r)) => self.registers[r] += 1,
+            Instruction::Dec(Operand::Register(r)) => self.registers[r] -= 1,
+            Instruction::Jnz(x, y) if self.value(x) != 0 => {
+                self.pc += self.value(y);
+                return None;
+            }
+            Instruction::Tgl(x) => {
+                let target = self.pc + self.value(x);
+
+                if let Some(i) = usize::try_from(target)
+                    .ok()
+                    .filter(|&i| i < self.program.len())
+                {
+                    self.program[i] = self.program[i].toggle();
+                    self.optimise_program();
+                }
+            }
+            Instruction::Out(x) => output = Some(self.value(x)),
+            // Toggling can produce instructions like `cpy 1 2`, which are skipped.
+            _ => {}
+        }
+
+        self.pc += 1;
+        output
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Output {
+    Value(i64),
+    Halted,
+    /// Looping forever without another output.
+    Stuck,
+}
+
+#[derive(PartialEq, Eq, Hash, Debug)]
+pub struct Snapshot {
+    pc: i64,
+    registers: [i64; 4],
+    program: Vec<Instruction>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from() {
+        assert_eq!(
+            Instruction::from("cpy 41 a"),
+            Ok((
+                "",
+                Instruction::Cpy(Operand::Value(41), Operand::Register(0))
+            ))
+        );
+        assert_eq!(
+            Instruction::from("jnz c -2"),
+            Ok((
+                "",
+                Instruction::Jnz(Operand::Register(2), Operand::Value(-2))
+            ))
+        );
+        assert_eq!(
+            Instruction::from("tgl d"),
+            Ok(("", Instruction::Tgl(Operand::Register(3))))
+        );
+        assert_eq!(
+            Instruction::from("out b"),
+            Ok(("", Instruction::Out(Operand::Register(1))))
+        );
+        assert!(Instruction::from("mul a b").is_err());
+    }
+
+    #[test]
+    fn test_toggle() {
+        let program = parse_program("cpy 2 a\ntgl a\ntgl a\ntgl a\ncpy 1 a\ndec a\ndec a");
+        let mut machine = Machine::new(program);
+        machine.run();
+
+        assert_eq!(machine.registers[0], 3);
+    }
+
+    #[test]
+    fn test_next_output() {
+        let mut machine = Machine::new(parse_program("out 1\ncpy 5 a\nout a\ntgl a"));
+
+        assert_eq!(machine.next_output(), Output::Value(1));
+        assert_eq!(machine.next_output(), Output::Value(5));
+        assert_eq!(machine.next_output(), Output::Halted);
+
+        let mut machine = Machine::new(parse_program("out 1\ncpy 1 b\njnz b -1"));
+
+        assert_eq!(machine.next_output(), Output::Value(1));
+        assert_eq!(machine.next_output(), Output::Stuck);
+
+        // Toggling the same line back and forth still comes round again.
+        let mut machine = Machine::new(parse_program("tgl 1\ninc a\njnz 1 -2"));
+
+        assert_eq!(machine.next_output(), Output::Stuck);
+
+        // Counting down revisits instructions but never a whole state.
+        let mut machine = Machine::new(parse_program("cpy 3 a\ndec a\njnz a -1\nout a"));
+
+        assert_eq!(machine.next_output(), Output::Value(0));
+    }
+
+    #[test]
+    fn test_find_macro() {
+        let program = parse_program("cpy b c\ninc a\ndec c\njnz c -2\ndec d\njnz d -5");
+
+        assert_eq!(
+            find_macro(&program),
+            Some(Macro::Multiply {
+                target: 0,
+                source: Operand::Register(1),
+                inner: 2,
+                outer: 3
+            })
+        );
+        assert_eq!(
+            find_macro(&program[1..]),
+            Some(Macro::Add {
+                target: 0,
+                counter: 2
+            })
+        );
+        assert_eq!(find_macro(&program[2..]), None);
+
+        // The source is overwritten by the loop, so it isn't a plain product.
+        let program = parse_program("cpy a c\ninc a\ndec c\njnz c -2\ndec d\njnz d -5");
+        assert_eq!(find_macro(&program), None);
+    }
+
+    #[test]
+    fn test_multiplication_matches_unoptimised() {
+        let input = "cpy 7 b\ncpy 6 d\ncpy b c\ninc a\ndec c\njnz c -2\ndec d\njnz d -5\ninc a";
+
+        let mut fast = Machine::new(parse_program(input));
+        fast.run();
+        let mut slow = Machine::new(parse_program(input)).without_optimisation();
+        slow.run();
+
+        assert_eq!(fast.registers, [43, 7, 0, 0]);
+        assert_eq!(fast.registers, slow.registers);
+    }
+
+    #[test]
+    fn test_toggle_inside_optimised_region() {
+        // The `tgl` turns `dec c` into `inc c` once, after which the block is
+        // no longer a multiplication and must run instruction by instruction.
+        let input =
+            "cpy 3 d\ncpy 4 b\ncpy 4 a\ntgl a\ncpy b c\ninc a\ndec c\njnz c -2\ndec d\njnz d -5";
+
+        let mut fast = Machine::new(parse_program(input));
+        fast.run();
+        let mut slow = Machine::new(parse_program(input)).without_optimisation();
+        slow.run();
+
+        assert_eq!(fast.registers, slow.registers);
+    }
+}
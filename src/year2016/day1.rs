@@ -0,0 +1,753 @@
+use std::{collections::HashSet, fmt};
+
+use nom::{
+    branch::alt,
+    character::complete::{char, multispace0, u32},
+    combinator::{all_consuming, map},
+    multi::separated_list1,
+    sequence::{delimited, tuple},
+    IResult,
+};
+
+pub mod render;
+pub mod turtle;
+
+pub fn solve(input: &str) -> Vec<String> {
+    let instructions = parse(input).unwrap_or_else(|e| panic!("{}", e));
+
+    let end = segments(&instructions).last().map_or((0, 0), |s| s.end);
+
+    let p2 = first_revisit(&instructions).unwrap();
+
+    vec![distance(end).to_string(), distance(p2).to_string()]
+}
+
+/// Parses the whole comma-separated list, allowing any whitespace or line
+/// breaks around the commas and rejecting anything left over.
+pub fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    let separator = || tuple((multispace0, char(','), multispace0));
+
+    all_consuming(delimited(
+        multispace0,
+        separated_list1(separator(), Instruction::from),
+        multispace0,
+    ))(input)
+    .map(|(_, instructions)| instructions)
+    .map_err(|e| {
+        let rest = match e {
+            nom::Err::Error(e) | nom::Err::Failure(e) => e.input,
+            nom::Err::Incomplete(_) => "",
+        };
+
+        // The list stops in front of a separator when the token after it
+        // doesn't parse, so that token is the one to blame.
+        let rest = separator()(rest).map_or(rest, |(after, _)| after);
+
+        ParseError::new(input, rest)
+    })
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// Byte offset of the offending token.
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+    /// Empty if the input ended too early.
+    pub token: String,
+}
+
+impl ParseError {
+    fn new(input: &str, rest: &str) -> Self {
+        let is_separator = |c: char| c == ',' || c.is_whitespace();
+
+        // nom may have given up part way through a token like `R2x`.
+        let offset = input[..input.len() - rest.len()]
+            .char_indices()
+            .rev()
+            .find(|&(_, c)| is_separator(c))
+            .map_or(0, |(i, c)| i + c.len_utf8());
+        let (before, rest) = input.split_at(offset);
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        let token = match rest.find(is_separator) {
+            Some(0) => &rest[..rest.chars().next().unwrap().len_utf8()],
+            Some(end) => &rest[..end],
+            None => rest,
+        };
+
+        ParseError {
+            offset,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            token: token.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.token.is_empty() {
+            write!(f, "unexpected end of input")?;
+        } else {
+            write!(f, "unexpected `{}`", self.token)?;
+        }
+
+        write!(f, " at line {}, column {}", self.line, self.column)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn distance((x, y): (i64, i64)) -> u64 {
+    x.unsigned_abs() + y.unsigned_abs()
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Direction {
+    North,
+    West,
+    East,
+    South,
+}
+
+impl Direction {
+    fn turn(self, instruction: Instruction) -> Direction {
+        match (self, instruction) {
+            (Direction::North, Instruction::Left(_)) => Direction::West,
+            (Direction::West, Instruction::Left(_)) => Direction::South,
+            (Direction::South, Instruction::Left(_)) => Direction::East,
+            (Direction::East, Instruction::Left(_)) => Direction::North,
+            (Direction::North, Instruction::Right(_)) => Direction::East,
+            (Direction::East, Instruction::Right(_)) => Direction::South,
+            (Direction::South, Instruction::Right(_)) => Direction::West,
+            (Direction::West, Instruction::Right(_)) => Direction::North,
+        }
+    }
+
+    fn offset(self) -> (i64, i64) {
+        match self {
+            Direction::North => (0, 1),
+            Direction::West => (-1, 0),
+            Direction::East => (1, 0),
+            Direction::South => (0, -1),
+        }
+    }
+}
+
+/// Walks one block at a time, remembering every visited location.
+pub struct Pointer {
+    x: i64,
+    y: i64,
+    direction: Direction,
+    history: HashSet<(i64, i64)>,
+    first_repetition: Option<(i64, i64)>,
+    // Where each instruction started and ended, in order.
+    corners: Vec<(i64, i64)>,
+}
+
+impl Default for Pointer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pointer {
+    pub fn new() -> Self {
+        Pointer {
+            x: 0,
+            y: 0,
+            direction: Direction::North,
+            history: HashSet::from([(0, 0)]),
+            first_repetition: None,
+            corners: vec![(0, 0)],
+        }
+    }
+
+    pub fn walk(instructions: &[Instruction]) -> Self {
+        let mut pointer = Pointer::new();
+        instructions
+            .iter()
+            .for_each(|&i| pointer.execute_instruction(i));
+        pointer
+    }
+
+    pub fn execute_instruction(&mut self, instruction: Instruction) {
+        let steps = instruction.steps() as i64;
+
+        // Turn
+        self.direction = self.direction.turn(instruction);
+
+        let (dx, dy) = self.direction.offset();
+
+        // Once the first repetition is known the history no longer matters,
+        // so the whole distance can be covered at once.
+        if self.first_repetition.is_some() {
+            self.x = self.x.checked_add(dx * steps).expect("x overflows i64");
+            self.y = self.y.checked_add(dy * steps).expect("y overflows i64");
+            self.corners.push((self.x, self.y));
+            return;
+        }
+
+        // Step
+        for _ in 0..steps {
+            self.x = self.x.checked_add(dx).expect("x overflows i64");
+            self.y = self.y.checked_add(dy).expect("y overflows i64");
+
+            // Write to history
+            if self.first_repetition.is_none() {
+                if self.history.contains(&(self.x, self.y)) {
+                    self.first_repetition = Some((self.x, self.y));
+                } else {
+                    self.history.insert((self.x, self.y));
+                }
+            }
+        }
+
+        self.corners.push((self.x, self.y));
+    }
+
+    /// The fewest instructions reaching the same place from the origin.
+    pub fn shortest_instructions(&self) -> Vec<Instruction> {
+        shortest_route((0, 0), Direction::North, (self.x, self.y))
+    }
+
+    /// The fewest instructions leading back to the origin from here.
+    pub fn return_instructions(&self) -> Vec<Instruction> {
+        shortest_route((self.x, self.y), self.direction, (0, 0))
+    }
+}
+
+/// The fewest instructions walking from `from`, facing `facing`, to `to`.
+pub fn shortest_route(from: (i64, i64), facing: Direction, to: (i64, i64)) -> Vec<Instruction> {
+    // Every instruction turns, so the walk alternates between the two axes,
+    // starting with the one across `facing`. Each leg covers at most
+    // `u32::MAX` blocks, and legs that aren't needed on one axis walk zero
+    // blocks just to turn towards the other.
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let (first, second) = match facing {
+        Direction::North | Direction::South => (
+            (dx, Direction::East, Direction::West),
+            (dy, Direction::North, Direction::South),
+        ),
+        Direction::East | Direction::West => (
+            (dy, Direction::North, Direction::South),
+            (dx, Direction::East, Direction::West),
+        ),
+    };
+
+    let legs = |d: i64| d.unsigned_abs().div_ceil(u32::MAX as u64) as usize;
+    let count = (2 * legs(first.0))
+        .saturating_sub(1)
+        .max(2 * legs(second.0));
+
+    let mut remaining = [first.0.unsigned_abs(), second.0.unsigned_abs()];
+    let mut direction = facing;
+
+    (0..count)
+        .map(|i| {
+            let (d, positive, negative) = if i % 2 == 0 { first } else { second };
+            let target = if d < 0 { negative } else { positive };
+
+            let steps = remaining[i % 2].min(u32::MAX as u64) as u32;
+            remaining[i % 2] -= steps as u64;
+
+            let instruction = if direction.turn(Instruction::Left(0)) == target {
+                Instruction::Left(steps)
+            } else {
+                Instruction::Right(steps)
+            };
+
+            direction = target;
+            instruction
+        })
+        .collect()
+}
+
+/// A straight stretch of the walk, including both ends.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Segment {
+    pub start: (i64, i64),
+    pub end: (i64, i64),
+}
+
+impl Segment {
+    fn len(&self) -> i64 {
+        (self.end.0 - self.start.0).abs() + (self.end.1 - self.start.1).abs()
+    }
+
+    fn at(&self, t: i64) -> (i64, i64) {
+        let (dx, dy) = (
+            (self.end.0 - self.start.0).signum(),
+            (self.end.1 - self.start.1).signum(),
+        );
+
+        (self.start.0 + dx * t, self.start.1 + dy * t)
+    }
+
+    /// How many blocks after its start this segment first touches `other`.
+    /// The start itself is not considered, as it ends the previous segment.
+    fn first_crossing(&self, other: &Segment) -> Option<i64> {
+        self.overlap(other).map(|(low, _)| low)
+    }
+
+    /// The range of blocks after its start where this segment runs over `other`.
+    fn overlap(&self, other: &Segment) -> Option<(i64, i64)> {
+        // Both segments are axis-aligned, so `other` is a (flat) box and each
+        // axis limits the steps along `self` that can land inside it.
+        let along = |start: i64, end: i64, low: i64, high: i64| match (end - start).signum() {
+            0 if (low..=high).contains(&start) => Some((i64::MIN, i64::MAX)),
+            0 => None,
+            1 => Some((low - start, high - start)),
+            _ => Some((start - high, start - low)),
+        };
+
+        let (x_low, x_high) = along(
+            self.start.0,
+            self.end.0,
+            other.start.0.min(other.end.0),
+            other.start.0.max(other.end.0),
+        )?;
+        let (y_low, y_high) = along(
+            self.start.1,
+            self.end.1,
+            other.start.1.min(other.end.1),
+            other.start.1.max(other.end.1),
+        )?;
+
+        let low = x_low.max(y_low).max(1);
+        let high = x_high.min(y_high).min(self.len());
+
+        (low <= high).then_some((low, high))
+    }
+}
+
+/// The walk as one segment per instruction, in constant time per instruction
+/// however many blocks it covers.
+pub fn segments(instructions: &[Instruction]) -> Vec<Segment> {
+    let mut direction = Direction::North;
+    let mut position = (0, 0);
+
+    instructions
+        .iter()
+        .map(|&instruction| {
+            direction = direction.turn(instruction);
+
+            let (dx, dy) = direction.offset();
+            let steps = instruction.steps() as i64;
+            let start = position;
+            position = (start.0 + dx * steps, start.1 + dy * steps);
+
+            Segment {
+                start,
+                end: position,
+            }
+        })
+        .collect()
+}
+
+/// Same answer as `Pointer::first_repetition`, but found by intersecting each
+/// segment with the ones before it. Takes O(n²) in the number of instructions
+/// and nothing in the number of blocks walked.
+pub fn first_revisit(instructions: &[Instruction]) -> Option<(i64, i64)> {
+    let segments = segments(instructions);
+
+    segments.iter().enumerate().find_map(|(i, segment)| {
+        segments[..i]
+            .iter()
+            .filter_map(|other| segment.first_crossing(other))
+            .min()
+            .map(|t| segment.at(t))
+    })
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Revisit {
+    pub location: (i64, i64),
+    /// Which visit to `location` this is, counting the first one as 1.
+    pub visit: u32,
+    /// Index of the instruction that was being walked.
+    pub instruction: usize,
+}
+
+/// Every block walked onto for a second time or more, in walking order.
+/// Like `first_revisit` the cost grows with the number of instructions, plus
+/// one entry for each revisited block.
+pub fn revisits(instructions: &[Instruction]) -> Vec<Revisit> {
+    let segments = segments(instructions);
+    let mut revisits = vec![];
+
+    for (i, segment) in segments.iter().enumerate() {
+        // Each earlier segment counts its blocks except for its start, which
+        // belongs to the segment before it. The origin is counted separately.
+        let mut events: Vec<(i64, i32)> = segments[..i]
+            .iter()
+            .filter_map(|other| {
+                let (mut low, mut high) = segment.overlap(other)?;
+
+                if segment.at(low) == other.start {
+                    low += 1;
+                } else if segment.at(high) == other.start {
+                    high -= 1;
+                }
+
+                (low <= high).then_some((low, high))
+            })
+            .chain(segment.overlap(&Segment {
+                start: (0, 0),
+                end: (0, 0),
+            }))
+            .flat_map(|(low, high)| [(low, 1), (high + 1, -1)])
+            .collect();
+
+        events.sort();
+
+        let mut earlier_visits = 0;
+
+        for pair in events.windows(2) {
+            earlier_visits += pair[0].1;
+
+            if earlier_visits > 0 {
+                revisits.extend((pair[0].0..pair[1].0).map(|t| Revisit {
+                    location: segment.at(t),
+                    visit: earlier_visits as u32 + 1,
+                    instruction: i,
+                }));
+            }
+        }
+    }
+
+    revisits
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Instruction {
+    Left(u32),
+    Right(u32),
+}
+
+impl Instruction {
+    fn steps(&self) -> u32 {
+        match self {
+            Instruction::Left(n) | Instruction::Right(n) => *n,
+        }
+    }
+
+    fn from(input: &str) -> IResult<&str, Instruction> {
+        alt((
+            map(tuple((char('L'), u32)), |(_, num)| Instruction::Left(num)),
+            map(tuple((char('R'), u32)), |(_, num)| Instruction::Right(num)),
+        ))(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::rng::Lcg;
+
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(Instruction::from("L10"), Ok(("", Instruction::Left(10))));
+        assert_eq!(Instruction::from("R20"), Ok(("", Instruction::Right(20))));
+        assert!(Instruction::from("C20").is_err());
+        assert!(Instruction::from("L-20").is_err());
+        assert!(Instruction::from("L").is_err());
+        assert_eq!(Instruction::from("R300"), Ok(("", Instruction::Right(300))));
+        assert_eq!(
+            Instruction::from("L4294967295"),
+            Ok(("", Instruction::Left(u32::MAX)))
+        );
+        assert!(Instruction::from("L4294967296").is_err());
+    }
+
+    #[test]
+    fn test_parse() {
+        let expected = vec![
+            Instruction::Right(5),
+            Instruction::Left(3),
+            Instruction::Right(12),
+        ];
+
+        assert_eq!(parse("R5, L3, R12"), Ok(expected.clone()));
+        assert_eq!(parse("R5, L3, R12\n"), Ok(expected.clone()));
+        assert_eq!(parse("R5,L3 ,\n\tR12"), Ok(expected.clone()));
+        assert_eq!(parse("  R5 , L3,R12  \r\n"), Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |input| parse(input).map_err(|e| (e.offset, e.line, e.column, e.token));
+
+        assert_eq!(error("R5, X3"), Err((4, 1, 5, "X3".to_string())));
+        assert_eq!(error("R5 L3"), Err((3, 1, 4, "L3".to_string())));
+        assert_eq!(error("R5, L3,\nR2x, L1"), Err((8, 2, 1, "R2x".to_string())));
+        assert_eq!(error("R5,, L3"), Err((3, 1, 4, ",".to_string())));
+        assert_eq!(error("R5, L3,"), Err((7, 1, 8, "".to_string())));
+        assert_eq!(error(""), Err((0, 1, 1, "".to_string())));
+        assert_eq!(
+            error("R4294967296"),
+            Err((0, 1, 1, "R4294967296".to_string()))
+        );
+
+        assert_eq!(
+            parse("R5, L3 garbage").unwrap_err().to_string(),
+            "unexpected `garbage` at line 1, column 8"
+        );
+        assert_eq!(
+            parse("R5,").unwrap_err().to_string(),
+            "unexpected end of input at line 1, column 4"
+        );
+    }
+
+    #[test]
+    fn test_execute() {
+        let mut pointer = Pointer::new();
+
+        pointer.execute_instruction(Instruction::Left(10));
+        assert_eq!(pointer.x, -10);
+        assert_eq!(pointer.y, 0);
+
+        pointer.execute_instruction(Instruction::Left(10));
+        assert_eq!(pointer.x, -10);
+        assert_eq!(pointer.x, -10);
+    }
+
+    #[test]
+    fn test_execute_long_walk() {
+        let mut pointer = Pointer::new();
+
+        for instruction in [
+            Instruction::Right(300),
+            Instruction::Right(300),
+            Instruction::Right(300),
+            Instruction::Right(600),
+        ] {
+            pointer.execute_instruction(instruction);
+        }
+
+        assert_eq!((pointer.x, pointer.y), (0, 300));
+        assert_eq!(pointer.first_repetition, Some((0, 0)));
+
+        pointer.execute_instruction(Instruction::Left(u32::MAX));
+        pointer.execute_instruction(Instruction::Left(u32::MAX));
+
+        assert_eq!(
+            (pointer.x, pointer.y),
+            (-(u32::MAX as i64), 300 - u32::MAX as i64)
+        );
+        assert_eq!(distance((pointer.x, pointer.y)), 2 * u32::MAX as u64 - 300);
+    }
+
+    #[test]
+    fn test_first_revisit() {
+        let instructions = [
+            Instruction::Right(8),
+            Instruction::Right(4),
+            Instruction::Right(4),
+            Instruction::Right(8),
+        ];
+
+        assert_eq!(first_revisit(&instructions), Some((4, 0)));
+        assert_eq!(first_revisit(&instructions[..3]), None);
+    }
+
+    #[test]
+    fn test_first_revisit_billions() {
+        let instructions = [
+            Instruction::Right(3_000_000_000),
+            Instruction::Right(2_000_000_000),
+            Instruction::Right(1_000_000_000),
+            Instruction::Right(4_000_000_000),
+        ];
+
+        assert_eq!(first_revisit(&instructions), Some((2_000_000_000, 0)));
+        assert_eq!(
+            segments(&instructions).last().unwrap().end,
+            (2_000_000_000, 2_000_000_000)
+        );
+    }
+
+    // Up to a dozen turns, each walking fewer than `steps` blocks.
+    fn random_instructions(rng: &mut Lcg, steps: usize) -> Vec<Instruction> {
+        (0..rng.below(12))
+            .map(|_| match rng.below(2) {
+                0 => Instruction::Left(rng.below(steps) as u32),
+                _ => Instruction::Right(rng.below(steps) as u32),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_first_revisit_matches_pointer() {
+        // Small random walks, including zero-length moves and walking back
+        // over the previous segment.
+        let mut rng = Lcg::new(1);
+
+        for _ in 0..500 {
+            let instructions = random_instructions(&mut rng, 8);
+
+            let pointer = Pointer::walk(&instructions);
+
+            assert_eq!(first_revisit(&instructions), pointer.first_repetition);
+            assert_eq!(
+                segments(&instructions).last().map_or((0, 0), |s| s.end),
+                (pointer.x, pointer.y)
+            );
+        }
+    }
+
+    #[test]
+    fn test_revisits() {
+        let instructions = [
+            Instruction::Right(8),
+            Instruction::Right(4),
+            Instruction::Right(4),
+            Instruction::Right(8),
+        ];
+
+        assert_eq!(
+            revisits(&instructions),
+            vec![Revisit {
+                location: (4, 0),
+                visit: 2,
+                instruction: 3
+            }]
+        );
+
+        // Back along the first segment to the origin, then out over it again.
+        let instructions = [
+            Instruction::Right(3),
+            Instruction::Left(0),
+            Instruction::Left(3),
+            Instruction::Left(0),
+            Instruction::Left(2),
+        ];
+
+        assert_eq!(
+            revisits(&instructions)
+                .iter()
+                .map(|r| (r.location, r.visit, r.instruction))
+                .collect::<Vec<_>>(),
+            vec![
+                ((2, 0), 2, 2),
+                ((1, 0), 2, 2),
+                ((0, 0), 2, 2),
+                ((1, 0), 3, 4),
+                ((2, 0), 3, 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_revisits_matches_walking() {
+        let mut rng = Lcg::new(7);
+
+        for _ in 0..500 {
+            let instructions = random_instructions(&mut rng, 8);
+
+            let mut visits = HashMap::from([((0, 0), 1)]);
+            let mut expected = vec![];
+
+            for (i, segment) in segments(&instructions).iter().enumerate() {
+                for t in 1..=segment.len() {
+                    let count = visits.entry(segment.at(t)).or_insert(0);
+                    *count += 1;
+
+                    if *count > 1 {
+                        expected.push(Revisit {
+                            location: segment.at(t),
+                            visit: *count,
+                            instruction: i,
+                        });
+                    }
+                }
+            }
+
+            assert_eq!(revisits(&instructions), expected);
+            assert_eq!(
+                revisits(&instructions).first().map(|r| r.location),
+                first_revisit(&instructions)
+            );
+        }
+    }
+
+    #[test]
+    fn test_shortest_instructions() {
+        let pointer = Pointer::walk(&[
+            Instruction::Right(5),
+            Instruction::Left(5),
+            Instruction::Right(5),
+            Instruction::Right(3),
+        ]);
+
+        assert_eq!(
+            pointer.shortest_instructions(),
+            vec![Instruction::Right(10), Instruction::Left(2)]
+        );
+        assert_eq!(
+            pointer.return_instructions(),
+            vec![Instruction::Right(10), Instruction::Left(2)]
+        );
+
+        assert_eq!(Pointer::new().shortest_instructions(), vec![]);
+        assert_eq!(
+            shortest_route((0, 0), Direction::North, (0, -4)),
+            vec![Instruction::Right(0), Instruction::Right(4)]
+        );
+    }
+
+    #[test]
+    fn test_shortest_route_beyond_u32() {
+        let far = 5_000_000_000;
+        let route = shortest_route((0, 0), Direction::North, (far, -3));
+
+        assert_eq!(
+            route,
+            vec![
+                Instruction::Right(u32::MAX),
+                Instruction::Right(3),
+                Instruction::Left((far - u32::MAX as i64) as u32),
+            ]
+        );
+        assert_eq!(segments(&route).last().unwrap().end, (far, -3));
+    }
+
+    #[test]
+    fn test_shortest_instructions_round_trip() {
+        let mut rng = Lcg::new(3);
+
+        for _ in 0..500 {
+            let instructions = random_instructions(&mut rng, 20);
+
+            let mut pointer = Pointer::walk(&instructions);
+            let shortest = pointer.shortest_instructions();
+
+            assert!(shortest.len() <= instructions.len());
+
+            let copy = Pointer::walk(&shortest);
+            assert_eq!((copy.x, copy.y), (pointer.x, pointer.y));
+
+            for instruction in pointer.return_instructions() {
+                pointer.execute_instruction(instruction);
+            }
+            assert_eq!((pointer.x, pointer.y), (0, 0));
+        }
+    }
+
+    #[test]
+    fn test_execute_many_steps() {
+        let mut pointer = Pointer::new();
+
+        // A spiral that never crosses itself, walking a quarter of a million blocks.
+        for n in 1..500 {
+            pointer.execute_instruction(Instruction::Right(n * 2));
+        }
+
+        assert_eq!(pointer.first_repetition, None);
+        assert_eq!(pointer.history.len(), 249_501);
+    }
+}
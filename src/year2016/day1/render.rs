@@ -0,0 +1,224 @@
+use std::fmt::Write;
+
+use super::Pointer;
+
+struct Bounds {
+    min_x: i64,
+    max_x: i64,
+    min_y: i64,
+    max_y: i64,
+}
+
+impl Bounds {
+    fn of(points: &[(i64, i64)]) -> Self {
+        points.iter().fold(
+            Bounds {
+                min_x: 0,
+                max_x: 0,
+                min_y: 0,
+                max_y: 0,
+            },
+            |b, &(x, y)| Bounds {
+                min_x: b.min_x.min(x),
+                max_x: b.max_x.max(x),
+                min_y: b.min_y.min(y),
+                max_y: b.max_y.max(y),
+            },
+        )
+    }
+
+    fn width(&self) -> i64 {
+        self.max_x - self.min_x
+    }
+
+    fn height(&self) -> i64 {
+        self.max_y - self.min_y
+    }
+}
+
+/// The route as an SVG polyline, with north pointing up. The start is marked
+/// green, the end red and the first revisited location blue.
+pub fn svg(pointer: &Pointer) -> String {
+    let bounds = Bounds::of(&pointer.corners);
+    let size = bounds.width().max(bounds.height()).max(1);
+    let margin = size / 20 + 1;
+    let stroke = size as f64 / 300.0;
+
+    let mut svg = String::new();
+
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}">"#,
+        bounds.min_x - margin,
+        -bounds.max_y - margin,
+        bounds.width() + 2 * margin,
+        bounds.height() + 2 * margin,
+    )
+    .unwrap();
+
+    let points: Vec<String> = pointer
+        .corners
+        .iter()
+        .map(|(x, y)| format!("{},{}", x, -y))
+        .collect();
+
+    writeln!(
+        svg,
+        r#"  <polyline points="{}" fill="none" stroke="black" stroke-width="{}" />"#,
+        points.join(" "),
+        stroke
+    )
+    .unwrap();
+
+    let markers = [
+        ("start", Some((0, 0)), "green"),
+        ("end", Some((pointer.x, pointer.y)), "red"),
+        ("first revisit", pointer.first_repetition, "blue"),
+    ];
+
+    for (title, point, colour) in markers {
+        if let Some((x, y)) = point {
+            writeln!(
+                svg,
+                r#"  <circle cx="{}" cy="{}" r="{}" fill="{}"><title>{}</title></circle>"#,
+                x,
+                -y,
+                stroke * 4.0,
+                colour,
+                title
+            )
+            .unwrap();
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// The route drawn with `-`, `|` and `+`, scaled down evenly on both axes to
+/// fit in `width` by `height` characters. `S` marks the start, `E` the end
+/// and `X` the first revisited location.
+pub fn ascii(pointer: &Pointer, width: usize, height: usize) -> String {
+    let bounds = Bounds::of(&pointer.corners);
+    let scale = |span: i64, cells: usize| (span + cells as i64 - 2) / (cells as i64 - 1).max(1);
+    let scale = scale(bounds.width(), width)
+        .max(scale(bounds.height(), height))
+        .max(1);
+
+    let cell = |(x, y): (i64, i64)| {
+        (
+            ((x - bounds.min_x) / scale) as usize,
+            ((bounds.max_y - y) / scale) as usize,
+        )
+    };
+
+    let (columns, rows) = cell((bounds.max_x, bounds.min_y));
+    let mut grid = vec![vec![' '; columns + 1]; rows + 1];
+
+    let mut draw = |(column, row): (usize, usize), c: char| {
+        let existing = &mut grid[row][column];
+        *existing = match *existing {
+            ' ' => c,
+            e if e == c => c,
+            _ => '+',
+        };
+    };
+
+    for pair in pointer.corners.windows(2) {
+        let (from, to) = (cell(pair[0]), cell(pair[1]));
+
+        if pair[0].1 == pair[1].1 {
+            for column in from.0.min(to.0)..=from.0.max(to.0) {
+                draw((column, from.1), '-');
+            }
+        } else {
+            for row in from.1.min(to.1)..=from.1.max(to.1) {
+                draw((from.0, row), '|');
+            }
+        }
+    }
+
+    let markers = [
+        (Some((0, 0)), 'S'),
+        (Some((pointer.x, pointer.y)), 'E'),
+        (pointer.first_repetition, 'X'),
+    ];
+
+    for (point, marker) in markers {
+        if let Some(point) = point {
+            let (column, row) = cell(point);
+            grid[row][column] = marker;
+        }
+    }
+
+    let mut ascii = format!("1 character = {} block(s)\n", scale);
+
+    for row in grid {
+        ascii.push_str(row.iter().collect::<String>().trim_end());
+        ascii.push('\n');
+    }
+
+    ascii
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Instruction;
+    use super::*;
+
+    fn example() -> Pointer {
+        Pointer::walk(&[
+            Instruction::Right(8),
+            Instruction::Right(4),
+            Instruction::Right(4),
+            Instruction::Right(8),
+        ])
+    }
+
+    #[test]
+    fn test_ascii() {
+        assert_eq!(
+            ascii(&example(), 80, 40),
+            "1 character = 1 block(s)
+    E
+    |
+    |
+    |
+S---X---+
+    |   |
+    |   |
+    |   |
+    +---+
+"
+        );
+    }
+
+    #[test]
+    fn test_ascii_scaled() {
+        let pointer = Pointer::walk(&[Instruction::Right(1000), Instruction::Left(500)]);
+
+        assert_eq!(
+            ascii(&pointer, 11, 11),
+            "1 character = 100 block(s)
+          E
+          |
+          |
+          |
+          |
+S---------+
+"
+        );
+    }
+
+    #[test]
+    fn test_svg() {
+        let svg = svg(&example());
+
+        assert!(
+            svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="-1 -5 10 10">"#)
+        );
+        assert!(svg.contains(r#"points="0,0 8,0 8,4 4,4 4,-4""#));
+        assert!(svg.contains(r#"<circle cx="4" cy="0""#));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+}
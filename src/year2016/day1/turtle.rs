@@ -0,0 +1,234 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, space0, u32},
+    combinator::map,
+    multi::separated_list1,
+    sequence::{preceded, tuple},
+    IResult,
+};
+
+/// The shape of the grid the turtle walks on. Headings are numbered
+/// clockwise from north.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Topology {
+    /// Four directions, measured in Manhattan distance.
+    Square,
+    /// Four straight and four diagonal directions, measured in Chebyshev
+    /// distance.
+    Octagonal,
+    /// Hexagons with flat tops, in axial coordinates where north is `(0, 1)`
+    /// and north-east is `(1, 0)`.
+    Hex,
+}
+
+impl Topology {
+    fn headings(self) -> &'static [(&'static str, (i64, i64))] {
+        match self {
+            Topology::Square => &[("N", (0, 1)), ("E", (1, 0)), ("S", (0, -1)), ("W", (-1, 0))],
+            Topology::Octagonal => &[
+                ("N", (0, 1)),
+                ("NE", (1, 1)),
+                ("E", (1, 0)),
+                ("SE", (1, -1)),
+                ("S", (0, -1)),
+                ("SW", (-1, -1)),
+                ("W", (-1, 0)),
+                ("NW", (-1, 1)),
+            ],
+            Topology::Hex => &[
+                ("N", (0, 1)),
+                ("NE", (1, 0)),
+                ("SE", (1, -1)),
+                ("S", (0, -1)),
+                ("SW", (-1, 0)),
+                ("NW", (-1, 1)),
+            ],
+        }
+    }
+
+    /// Headings per left or right turn unless configured otherwise: a right
+    /// angle on square grids and 60 degrees on hex grids.
+    fn default_turn(self) -> usize {
+        match self {
+            Topology::Square | Topology::Hex => 1,
+            Topology::Octagonal => 2,
+        }
+    }
+
+    fn distance(self, (x, y): (i64, i64)) -> u64 {
+        match self {
+            Topology::Square => x.unsigned_abs() + y.unsigned_abs(),
+            Topology::Octagonal => x.unsigned_abs().max(y.unsigned_abs()),
+            Topology::Hex => (x.unsigned_abs() + y.unsigned_abs() + (x + y).unsigned_abs()) / 2,
+        }
+    }
+}
+
+/// Every command walks the given number of blocks after changing heading.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Command {
+    Left(u32),
+    Right(u32),
+    UTurn(u32),
+    Forward(u32),
+    /// Face an absolute heading, numbered clockwise from north.
+    Face(usize, u32),
+}
+
+impl Command {
+    /// Parses `L5`, `R5`, `U5` (turn around), `F5` (keep heading) or a
+    /// heading named after the topology's compass points, like `NE5`.
+    pub fn from(topology: Topology, input: &str) -> IResult<&str, Command> {
+        alt((
+            map(preceded(char('L'), u32), Command::Left),
+            map(preceded(char('R'), u32), Command::Right),
+            map(preceded(char('U'), u32), Command::UTurn),
+            map(preceded(char('F'), u32), Command::Forward),
+            |input| heading(topology, input),
+        ))(input)
+    }
+}
+
+fn heading(topology: Topology, input: &str) -> IResult<&str, Command> {
+    for (index, (name, _)) in topology.headings().iter().enumerate() {
+        if let Ok((input, steps)) = preceded(tag(*name), u32::<&str, ()>)(input) {
+            return Ok((input, Command::Face(index, steps)));
+        }
+    }
+
+    Err(nom::Err::Error(nom::error::Error::new(
+        input,
+        nom::error::ErrorKind::Tag,
+    )))
+}
+
+pub fn parse_commands(topology: Topology, input: &str) -> IResult<&str, Vec<Command>> {
+    separated_list1(tuple((char(','), space0)), |input| {
+        Command::from(topology, input)
+    })(input)
+}
+
+pub struct Turtle {
+    topology: Topology,
+    turn: usize,
+    heading: usize,
+    pub position: (i64, i64),
+}
+
+impl Turtle {
+    pub fn new(topology: Topology) -> Self {
+        Turtle {
+            topology,
+            turn: topology.default_turn(),
+            heading: 0,
+            position: (0, 0),
+        }
+    }
+
+    /// Sets how many headings a left or right turn moves through, e.g. 1 for
+    /// 45 degree turns on an octagonal grid.
+    pub fn with_turn(mut self, headings: usize) -> Self {
+        self.turn = headings;
+        self
+    }
+
+    pub fn execute(&mut self, command: Command) {
+        let headings = self.topology.headings().len();
+
+        let (heading, steps) = match command {
+            Command::Left(n) => (
+                (self.heading + headings - self.turn % headings) % headings,
+                n,
+            ),
+            Command::Right(n) => ((self.heading + self.turn) % headings, n),
+            Command::UTurn(n) => ((self.heading + headings / 2) % headings, n),
+            Command::Forward(n) => (self.heading, n),
+            Command::Face(heading, n) => (heading % headings, n),
+        };
+
+        let (dx, dy) = self.topology.headings()[heading].1;
+
+        self.heading = heading;
+        self.position = (
+            self.position.0 + dx * steps as i64,
+            self.position.1 + dy * steps as i64,
+        );
+    }
+
+    /// Distance from the origin, measured the way the topology moves.
+    pub fn distance(&self) -> u64 {
+        self.topology.distance(self.position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn walk(turtle: Turtle, input: &str) -> Turtle {
+        let (rest, commands) = parse_commands(turtle.topology, input).unwrap();
+        assert_eq!(rest, "");
+
+        commands.into_iter().fold(turtle, |mut turtle, command| {
+            turtle.execute(command);
+            turtle
+        })
+    }
+
+    #[test]
+    fn test_from() {
+        assert_eq!(
+            Command::from(Topology::Square, "U3"),
+            Ok(("", Command::UTurn(3)))
+        );
+        assert_eq!(
+            Command::from(Topology::Octagonal, "NE12"),
+            Ok(("", Command::Face(1, 12)))
+        );
+        assert_eq!(
+            Command::from(Topology::Hex, "SW1"),
+            Ok(("", Command::Face(4, 1)))
+        );
+        assert!(Command::from(Topology::Square, "NE1").is_err());
+        assert!(Command::from(Topology::Hex, "E1").is_err());
+    }
+
+    #[test]
+    fn test_square() {
+        let turtle = walk(Turtle::new(Topology::Square), "R5, L5, R5, R3");
+        assert_eq!(turtle.distance(), 12);
+
+        let turtle = walk(Turtle::new(Topology::Square), "F2, U5, W1");
+        assert_eq!(turtle.position, (-1, -3));
+    }
+
+    #[test]
+    fn test_octagonal() {
+        let turtle = walk(Turtle::new(Topology::Octagonal), "R3, L2");
+        assert_eq!(turtle.position, (3, 2));
+        assert_eq!(turtle.distance(), 3);
+
+        let turtle = walk(Turtle::new(Topology::Octagonal).with_turn(1), "R3, F1, L4");
+        assert_eq!(turtle.position, (4, 8));
+        assert_eq!(turtle.distance(), 8);
+    }
+
+    #[test]
+    fn test_hex() {
+        for (input, distance) in [
+            ("NE1, NE1, NE1", 3),
+            ("NE1, NE1, SW1, SW1", 0),
+            ("NE2, S2", 2),
+            ("SE2, SW3", 3),
+        ] {
+            assert_eq!(walk(Turtle::new(Topology::Hex), input).distance(), distance);
+        }
+
+        let turtle = walk(Turtle::new(Topology::Hex), "R1, U1");
+        assert_eq!(turtle.position, (0, 0));
+
+        let turtle = walk(Turtle::new(Topology::Hex), "L1, L1, F1");
+        assert_eq!(turtle.position, (-3, 1));
+    }
+}
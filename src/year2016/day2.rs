@@ -0,0 +1,934 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+use crate::search::{self, State};
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, u32},
+    combinator::{all_consuming, map, opt},
+    multi::many1,
+    sequence::{delimited, pair},
+    IResult,
+};
+
+pub const SQUARE: &str = "
+1 2 3
+4 5 6
+7 8 9
+";
+
+pub const DIAMOND: &str = "
+    1
+  2 3 4
+5 6 7 8 9
+  A B C
+    D
+";
+
+pub fn solve(input: &str) -> Vec<String> {
+    let instructions = parse(input);
+
+    let mut part1 = Keyboard::from_layout(SQUARE, DEFAULT_START).unwrap();
+
+    let mut part2 = Keyboard::from_layout(DIAMOND, DEFAULT_START).unwrap();
+
+    vec![part1.code(&instructions), part2.code(&instructions)]
+}
+
+pub const DEFAULT_START: char = '5';
+
+pub fn parse(input: &str) -> Vec<Vec<Instruction>> {
+    input
+        .lines()
+        .map(parse_line)
+        .map(Result::unwrap)
+        .map(|(_, i)| i)
+        .collect()
+}
+
+/// Parses the extended format, where moves can be diagonal like `(UL)`,
+/// repeated like `U3`, and `*` presses the key under the pointer. A line
+/// without any `*` presses once at its end, so the original format reads
+/// the same either way.
+pub fn parse_extended(input: &str) -> Vec<Vec<Token>> {
+    input
+        .lines()
+        .map(|line| all_consuming(many1(Token::from))(line))
+        .map(Result::unwrap)
+        .map(|(_, t)| t)
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum KeyboardError {
+    EmptyLayout,
+    DuplicateKey(char),
+    UnknownKey(char),
+    OffPad((i32, i32)),
+    Unreachable(char),
+}
+
+impl fmt::Display for KeyboardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KeyboardError::EmptyLayout => write!(f, "the layout has no keys"),
+            KeyboardError::DuplicateKey(c) => write!(f, "more than one key is labelled {}", c),
+            KeyboardError::UnknownKey(c) => write!(f, "no key is labelled {}", c),
+            KeyboardError::OffPad((x, y)) => write!(f, "there is no key at ({}, {})", x, y),
+            KeyboardError::Unreachable(c) => write!(f, "key {} can't be reached", c),
+        }
+    }
+}
+
+impl std::error::Error for KeyboardError {}
+
+/// A byte in the raw input that isn't a move or a line break.
+#[derive(Debug, PartialEq, Eq)]
+pub struct InvalidByte {
+    pub offset: usize,
+    pub byte: u8,
+}
+
+impl fmt::Display for InvalidByte {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unexpected {:?} at offset {}",
+            self.byte as char, self.offset
+        )
+    }
+}
+
+impl std::error::Error for InvalidByte {}
+
+/// A keypad whose pointer is always on one of its keys.
+#[derive(Clone)]
+pub struct Keyboard {
+    keys: HashMap<(i32, i32), char>,
+    pointer: (i32, i32),
+}
+
+impl Keyboard {
+    /// Reads a keypad from a drawing where every character other than a
+    /// space is a key. Drawings with a space between neighbouring keys, like
+    /// `SQUARE` and `DIAMOND`, are recognised by all keys sharing the parity
+    /// of their column; a hole in such a drawing takes three spaces. The
+    /// pointer starts on the key labelled `start`.
+    pub fn from_layout(layout: &str, start: char) -> Result<Self, KeyboardError> {
+        let rows: Vec<&str> = layout
+            .lines()
+            .skip_while(|row| row.trim().is_empty())
+            .collect();
+
+        let mut keys: Vec<((i32, i32), char)> = rows
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.chars()
+                    .enumerate()
+                    .filter(|(_, c)| !c.is_whitespace())
+                    .map(move |(x, c)| ((x as i32, y as i32), c))
+            })
+            .collect();
+
+        let spaced = keys
+            .windows(2)
+            .all(|pair| (pair[0].0 .0 - pair[1].0 .0) % 2 == 0);
+
+        if spaced {
+            keys.iter_mut().for_each(|((x, _), _)| *x /= 2);
+        }
+
+        let mut labels = HashSet::new();
+        if let Some((_, c)) = keys.iter().find(|(_, c)| !labels.insert(*c)) {
+            return Err(KeyboardError::DuplicateKey(*c));
+        }
+
+        let pointer = keys
+            .first()
+            .map(|(position, _)| *position)
+            .ok_or(KeyboardError::EmptyLayout)?;
+
+        Keyboard {
+            keys: keys.into_iter().collect(),
+            pointer,
+        }
+        .with_start(start)
+    }
+
+    /// Moves the pointer to the key with the given label.
+    pub fn with_start(mut self, label: char) -> Result<Self, KeyboardError> {
+        self.pointer = *self
+            .keys
+            .iter()
+            .find(|(_, &c)| c == label)
+            .ok_or(KeyboardError::UnknownKey(label))?
+            .0;
+
+        Ok(self)
+    }
+
+    /// Moves the pointer to the key at the given position, counted in keys
+    /// from the top left of the drawing.
+    pub fn at_position(mut self, position: (i32, i32)) -> Result<Self, KeyboardError> {
+        if !self.keys.contains_key(&position) {
+            return Err(KeyboardError::OffPad(position));
+        }
+
+        self.pointer = position;
+        Ok(self)
+    }
+
+    /// Follows each line in turn and collects the key it ends on.
+    pub fn code(&mut self, lines: &[Vec<Instruction>]) -> String {
+        lines
+            .iter()
+            .map(|line| self.execute_instructions(line).1)
+            .collect()
+    }
+
+    /// Numbers the keys and works out every move up front, for
+    /// `CompiledKeyboard::execute_bytes`.
+    pub fn compile(&self) -> CompiledKeyboard {
+        let mut positions: Vec<(i32, i32)> = self.keys.keys().copied().collect();
+        positions.sort_by_key(|&(x, y)| (y, x));
+
+        let index: HashMap<(i32, i32), usize> = positions
+            .iter()
+            .enumerate()
+            .map(|(i, &position)| (position, i))
+            .collect();
+
+        CompiledKeyboard {
+            labels: positions.iter().map(|p| self.keys[p]).collect(),
+            moves: positions
+                .iter()
+                .map(|&p| Instruction::ALL.map(|instruction| index[&self.step(p, &instruction)]))
+                .collect(),
+            pointer: index[&self.pointer],
+        }
+    }
+
+    /// Like `code`, for lines in the extended format. Moves after the last
+    /// `*` of a line still carry over to the next one.
+    pub fn code_extended(&mut self, lines: &[Vec<Token>]) -> String {
+        let mut code = String::new();
+
+        for line in lines {
+            for token in line {
+                match token {
+                    Token::Move(instruction, times) => {
+                        for _ in 0..*times {
+                            self.pointer = self.step(self.pointer, instruction);
+                        }
+                    }
+                    Token::Press => code.push(self.keys[&self.pointer]),
+                }
+            }
+
+            if !line.contains(&Token::Press) {
+                code.push(self.keys[&self.pointer]);
+            }
+        }
+
+        code
+    }
+
+    /// Follows each line like `code`, recording every move on the way.
+    pub fn trace(&mut self, lines: &[Vec<Instruction>]) -> Vec<Vec<Step>> {
+        lines
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|instruction| {
+                        let from = self.pointer;
+                        self.pointer = self.step(from, instruction);
+
+                        Step {
+                            instruction: instruction.clone(),
+                            from: self.keys[&from],
+                            to: self.keys[&self.pointer],
+                            blocked: from == self.pointer,
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Draws the keypad with a space between keys, putting brackets around
+    /// the key labelled `highlight`.
+    pub fn draw(&self, highlight: char) -> String {
+        let width = self.keys.keys().map(|&(x, _)| x).max().unwrap_or(0);
+        let height = self.keys.keys().map(|&(_, y)| y).max().unwrap_or(0);
+
+        (0..=height)
+            .map(|y| {
+                let row: String = (0..=width)
+                    .map(|x| match self.keys.get(&(x, y)) {
+                        Some(&c) if c == highlight => format!("[{}]", c),
+                        Some(c) => format!(" {} ", c),
+                        None => "   ".to_string(),
+                    })
+                    .collect();
+
+                format!("{}\n", row.trim_end())
+            })
+            .collect()
+    }
+
+    /// One shortest line per character of `code`, starting from the pointer.
+    /// A line can't be empty, so pressing the same key twice takes a move
+    /// against an edge or, away from the edges, a step out and back.
+    pub fn instructions_for(&self, code: &str) -> Result<Vec<Vec<Instruction>>, KeyboardError> {
+        let mut from = self.pointer;
+
+        code.chars()
+            .map(|label| {
+                let to = self.position_of(label)?;
+                let path = search::bfs(self.finger(from), |f| f.moved && f.at == to)
+                    .solution
+                    .ok_or(KeyboardError::Unreachable(label))?
+                    .path;
+
+                from = to;
+                Ok(path.iter().filter_map(|f| f.instruction.clone()).collect())
+            })
+            .collect()
+    }
+
+    /// Every shortest line for each character of `code`, starting from the
+    /// pointer. Any combination of one line per character types the code.
+    pub fn all_instructions_for(
+        &self,
+        code: &str,
+    ) -> Result<Vec<Vec<Vec<Instruction>>>, KeyboardError> {
+        let mut from = self.pointer;
+
+        code.chars()
+            .map(|label| {
+                let to = self.position_of(label)?;
+                let distances = search::bfs_distances(self.finger(from));
+
+                from = to;
+                let steps = distances
+                    .get(&(to, true))
+                    .ok_or(KeyboardError::Unreachable(label))?;
+
+                Ok(self.lines_to(&distances, to, *steps))
+            })
+            .collect()
+    }
+
+    // Walks back from `to` through positions one move closer to the start.
+    fn lines_to(
+        &self,
+        distances: &HashMap<((i32, i32), bool), u32>,
+        to: (i32, i32),
+        steps: u32,
+    ) -> Vec<Vec<Instruction>> {
+        if steps == 0 {
+            return vec![vec![]];
+        }
+
+        let mut lines = vec![];
+
+        for (&(at, moved), &distance) in distances {
+            if distance + 1 != steps || (steps > 1) != moved {
+                continue;
+            }
+
+            for instruction in Instruction::ALL {
+                if self.step(at, &instruction) == to {
+                    for mut line in self.lines_to(distances, at, distance) {
+                        line.push(instruction.clone());
+                        lines.push(line);
+                    }
+                }
+            }
+        }
+
+        lines
+    }
+
+    fn position_of(&self, label: char) -> Result<(i32, i32), KeyboardError> {
+        self.keys
+            .iter()
+            .find(|(_, &c)| c == label)
+            .map(|(&position, _)| position)
+            .ok_or(KeyboardError::UnknownKey(label))
+    }
+
+    fn finger(&self, at: (i32, i32)) -> Finger<'_> {
+        Finger {
+            keyboard: self,
+            at,
+            moved: false,
+            instruction: None,
+        }
+    }
+
+    // Where the pointer ends up, staying put when the move would leave the pad.
+    fn step(&self, (x, y): (i32, i32), instruction: &Instruction) -> (i32, i32) {
+        let next = match instruction {
+            Instruction::Up => (x, y - 1),
+            Instruction::Down => (x, y + 1),
+            Instruction::Left => (x - 1, y),
+            Instruction::Right => (x + 1, y),
+            Instruction::UpLeft => (x - 1, y - 1),
+            Instruction::UpRight => (x + 1, y - 1),
+            Instruction::DownLeft => (x - 1, y + 1),
+            Instruction::DownRight => (x + 1, y + 1),
+        };
+
+        if self.keys.contains_key(&next) {
+            next
+        } else {
+            (x, y)
+        }
+    }
+
+    fn execute_instructions(&mut self, instructions: &Vec<Instruction>) -> (&mut Self, char) {
+        for i in instructions {
+            self.pointer = self.step(self.pointer, i);
+        }
+
+        let field = self.keys[&self.pointer];
+
+        (self, field)
+    }
+}
+
+/// A keypad turned into a table of where each move leads from each key.
+#[derive(Clone, Debug)]
+pub struct CompiledKeyboard {
+    labels: Vec<char>,
+    // Indexed by key, then by direction in the order of `Instruction::ALL`.
+    moves: Vec<[usize; 4]>,
+    pointer: usize,
+}
+
+impl CompiledKeyboard {
+    /// Same as `Keyboard::code`, but straight from the input bytes. Empty
+    /// lines don't press anything, and `\r` is ignored.
+    pub fn execute_bytes(&mut self, input: &[u8]) -> Result<String, InvalidByte> {
+        let mut code = String::new();
+        let mut pending = false;
+
+        for (offset, &byte) in input.iter().enumerate() {
+            let direction = match byte {
+                b'U' => 0,
+                b'D' => 1,
+                b'L' => 2,
+                b'R' => 3,
+                b'\n' => {
+                    if pending {
+                        code.push(self.labels[self.pointer]);
+                        pending = false;
+                    }
+                    continue;
+                }
+                b'\r' => continue,
+                _ => return Err(InvalidByte { offset, byte }),
+            };
+
+            self.pointer = self.moves[self.pointer][direction];
+            pending = true;
+        }
+
+        if pending {
+            code.push(self.labels[self.pointer]);
+        }
+
+        Ok(code)
+    }
+}
+
+/// One attempted move of the pointer.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Step {
+    pub instruction: Instruction,
+    pub from: char,
+    pub to: char,
+    /// The move would have left the pad, so `to` is the same key as `from`.
+    pub blocked: bool,
+}
+
+// A pointer position during the reverse search. `moved` tells a line that
+// hasn't started yet apart from one that has come back to its start.
+#[derive(Clone)]
+struct Finger<'a> {
+    keyboard: &'a Keyboard,
+    at: (i32, i32),
+    moved: bool,
+    instruction: Option<Instruction>,
+}
+
+impl State for Finger<'_> {
+    type Key = ((i32, i32), bool);
+
+    fn successors(&self) -> Vec<(Self, u32)> {
+        Instruction::ALL
+            .into_iter()
+            .map(|instruction| {
+                let finger = Finger {
+                    at: self.keyboard.step(self.at, &instruction),
+                    moved: true,
+                    instruction: Some(instruction),
+                    ..*self
+                };
+                (finger, 1)
+            })
+            .collect()
+    }
+
+    fn key(&self) -> Self::Key {
+        (self.at, self.moved)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Instruction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+fn parse_line(line: &str) -> IResult<&str, Vec<Instruction>> {
+    many1(Instruction::from)(line)
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Instruction::Up => "U",
+            Instruction::Down => "D",
+            Instruction::Left => "L",
+            Instruction::Right => "R",
+            Instruction::UpLeft => "(UL)",
+            Instruction::UpRight => "(UR)",
+            Instruction::DownLeft => "(DL)",
+            Instruction::DownRight => "(DR)",
+        };
+
+        write!(f, "{}", s)
+    }
+}
+
+impl Instruction {
+    /// The moves of the original format, which are all the reverse solver
+    /// uses.
+    pub const ALL: [Instruction; 4] = [
+        Instruction::Up,
+        Instruction::Down,
+        Instruction::Left,
+        Instruction::Right,
+    ];
+
+    fn from(input: &str) -> IResult<&str, Instruction> {
+        alt((
+            map(char('U'), |_| Instruction::Up),
+            map(char('D'), |_| Instruction::Down),
+            map(char('L'), |_| Instruction::Left),
+            map(char('R'), |_| Instruction::Right),
+        ))(input)
+    }
+
+    fn diagonal(input: &str) -> IResult<&str, Instruction> {
+        delimited(
+            char('('),
+            alt((
+                map(tag("UL"), |_| Instruction::UpLeft),
+                map(tag("UR"), |_| Instruction::UpRight),
+                map(tag("DL"), |_| Instruction::DownLeft),
+                map(tag("DR"), |_| Instruction::DownRight),
+            )),
+            char(')'),
+        )(input)
+    }
+}
+
+/// A piece of a line in the extended format.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Token {
+    /// Makes the same move a number of times.
+    Move(Instruction, u32),
+    Press,
+}
+
+impl Token {
+    fn from(input: &str) -> IResult<&str, Token> {
+        alt((
+            map(char('*'), |_| Token::Press),
+            map(
+                pair(alt((Instruction::diagonal, Instruction::from)), opt(u32)),
+                |(instruction, times)| Token::Move(instruction, times.unwrap_or(1)),
+            ),
+        ))(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Lcg;
+
+    #[test]
+    fn test_from() {
+        assert_eq!(Instruction::from("U"), Ok(("", Instruction::Up)));
+        assert_eq!(Instruction::from("D"), Ok(("", Instruction::Down)));
+        assert_eq!(Instruction::from("L"), Ok(("", Instruction::Left)));
+        assert_eq!(Instruction::from("R"), Ok(("", Instruction::Right)));
+        assert!(Instruction::from("C").is_err());
+    }
+    #[test]
+    fn test_from_layout() {
+        let square = Keyboard::from_layout(SQUARE, DEFAULT_START).unwrap();
+        assert_eq!(square.keys.len(), 9);
+        assert_eq!(square.keys[&(1, 1)], '5');
+        assert_eq!(square.pointer, (1, 1));
+
+        let diamond = Keyboard::from_layout(DIAMOND, DEFAULT_START).unwrap();
+        assert_eq!(diamond.keys.len(), 13);
+        assert_eq!(diamond.keys[&(2, 0)], '1');
+        assert_eq!(diamond.keys[&(0, 2)], '5');
+        assert_eq!(diamond.keys[&(2, 4)], 'D');
+        assert_eq!(diamond.pointer, (0, 2));
+
+        // Without spacing, a single space is a hole.
+        let holes = Keyboard::from_layout("ab\nc d", 'a').unwrap();
+        assert_eq!(holes.keys.len(), 4);
+        assert_eq!(holes.keys[&(2, 1)], 'd');
+        assert!(!holes.keys.contains_key(&(1, 1)));
+    }
+
+    #[test]
+    fn test_code() {
+        let instructions = parse("ULL\nRRDDD\nLURDL\nUUUUD");
+
+        let mut square = Keyboard::from_layout(SQUARE, DEFAULT_START).unwrap();
+        assert_eq!(square.code(&instructions), "1985");
+
+        let mut diamond = Keyboard::from_layout(DIAMOND, DEFAULT_START).unwrap();
+        assert_eq!(diamond.code(&instructions), "5DB3");
+    }
+
+    #[test]
+    fn test_start() {
+        let square = Keyboard::from_layout(SQUARE, '9').unwrap();
+        assert_eq!(square.pointer, (2, 2));
+
+        let square = square.at_position((0, 1)).unwrap();
+        assert_eq!(square.pointer, (0, 1));
+
+        assert_eq!(
+            Keyboard::from_layout(SQUARE, 'A').err(),
+            Some(KeyboardError::UnknownKey('A'))
+        );
+        assert_eq!(
+            Keyboard::from_layout(DIAMOND, '1')
+                .unwrap()
+                .at_position((0, 0))
+                .err(),
+            Some(KeyboardError::OffPad((0, 0)))
+        );
+        assert_eq!(
+            Keyboard::from_layout("1 2\n2 1", '1').err(),
+            Some(KeyboardError::DuplicateKey('2'))
+        );
+        assert_eq!(
+            Keyboard::from_layout("\n  \n", '5').err(),
+            Some(KeyboardError::EmptyLayout)
+        );
+    }
+
+    #[test]
+    fn test_trace() {
+        let mut square = Keyboard::from_layout(SQUARE, DEFAULT_START).unwrap();
+        let trace = square.trace(&parse("ULL\nRD"));
+
+        assert_eq!(
+            trace[0],
+            vec![
+                Step {
+                    instruction: Instruction::Up,
+                    from: '5',
+                    to: '2',
+                    blocked: false
+                },
+                Step {
+                    instruction: Instruction::Left,
+                    from: '2',
+                    to: '1',
+                    blocked: false
+                },
+                Step {
+                    instruction: Instruction::Left,
+                    from: '1',
+                    to: '1',
+                    blocked: true
+                },
+            ]
+        );
+        assert_eq!(trace[1].last().unwrap().to, '5');
+        assert_eq!(square.pointer, (1, 1));
+    }
+
+    #[test]
+    fn test_draw() {
+        let diamond = Keyboard::from_layout(DIAMOND, DEFAULT_START).unwrap();
+
+        assert_eq!(
+            diamond.draw('7'),
+            [
+                "       1",
+                "    2  3  4",
+                " 5  6 [7] 8  9",
+                "    A  B  C",
+                "       D",
+                ""
+            ]
+            .join("\n")
+        );
+    }
+
+    fn lines(code: &[Vec<Instruction>]) -> Vec<String> {
+        code.iter()
+            .map(|line| line.iter().map(Instruction::to_string).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_instructions_for() {
+        let square = Keyboard::from_layout(SQUARE, DEFAULT_START).unwrap();
+
+        // Pressing 5 again needs a step out and back, pressing 1 again a
+        // move against the edge.
+        let lines = square.instructions_for("1159").unwrap();
+        assert_eq!(
+            lines.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![2, 1, 2, 2]
+        );
+        assert_eq!(square.clone().code(&lines), "1159");
+
+        let diamond = Keyboard::from_layout(DIAMOND, DEFAULT_START).unwrap();
+        let lines = diamond.instructions_for("D1").unwrap();
+        assert_eq!(lines.iter().map(Vec::len).collect::<Vec<_>>(), vec![4, 4]);
+
+        assert_eq!(
+            square.instructions_for("12A").err(),
+            Some(KeyboardError::UnknownKey('A'))
+        );
+
+        let islands = Keyboard::from_layout("ab d", 'a').unwrap();
+        assert_eq!(
+            islands.instructions_for("bd").err(),
+            Some(KeyboardError::Unreachable('d'))
+        );
+        assert_eq!(
+            islands.all_instructions_for("d").err(),
+            Some(KeyboardError::Unreachable('d'))
+        );
+    }
+
+    #[test]
+    fn test_all_instructions_for() {
+        let square = Keyboard::from_layout(SQUARE, DEFAULT_START).unwrap();
+        let all = square.all_instructions_for("19").unwrap();
+
+        let mut first = lines(&all[0]);
+        first.sort();
+        assert_eq!(first, vec!["LU", "UL"]);
+
+        let mut second = lines(&all[1]);
+        second.sort();
+        assert_eq!(second, vec!["DDRR", "DRDR", "DRRD", "RDDR", "RDRD", "RRDD"]);
+
+        // Staying on 1 works with either edge.
+        let mut again = lines(
+            &square
+                .with_start('1')
+                .unwrap()
+                .all_instructions_for("1")
+                .unwrap()[0],
+        );
+        again.sort();
+        assert_eq!(again, vec!["L", "U"]);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut rng = Lcg::new(2016);
+
+        for layout in [SQUARE, DIAMOND] {
+            let keyboard = Keyboard::from_layout(layout, DEFAULT_START).unwrap();
+            let labels: Vec<char> = keyboard.keys.values().copied().collect();
+
+            for _ in 0..20 {
+                let code: String = (0..6).map(|_| *rng.choose(&labels)).collect();
+
+                let lines = keyboard.instructions_for(&code).unwrap();
+                assert_eq!(keyboard.clone().code(&lines), code);
+
+                for (line, all) in lines
+                    .iter()
+                    .zip(keyboard.all_instructions_for(&code).unwrap())
+                {
+                    assert!(all.contains(line));
+                    assert!(all.iter().all(|other| other.len() == line.len()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_token_from() {
+        assert_eq!(
+            Token::from("(UL)2R"),
+            Ok(("R", Token::Move(Instruction::UpLeft, 2)))
+        );
+        assert_eq!(
+            Token::from("U12*"),
+            Ok(("*", Token::Move(Instruction::Up, 12)))
+        );
+        assert_eq!(Token::from("*U"), Ok(("U", Token::Press)));
+        assert!(Token::from("(LU)").is_err());
+        assert!(Token::from("(U").is_err());
+    }
+
+    #[test]
+    fn test_code_extended() {
+        let mut diamond = Keyboard::from_layout(DIAMOND, DEFAULT_START).unwrap();
+        let lines = parse_extended("(UR)*(UR)*(DR)2*\nR4\n(DL)(DL)");
+        assert_eq!(diamond.code_extended(&lines), "2199D");
+
+        // Moves after the last press carry over.
+        let mut square = Keyboard::from_layout(SQUARE, DEFAULT_START).unwrap();
+        let lines = parse_extended("*U\nL*\n*D2R");
+        assert_eq!(square.code_extended(&lines), "511");
+        assert_eq!(square.pointer, (1, 2));
+    }
+
+    #[test]
+    fn test_extended_superset() {
+        let input = "ULL\nRRDDD\nLURDL\nUUUUD";
+
+        for layout in [SQUARE, DIAMOND] {
+            let keyboard = Keyboard::from_layout(layout, DEFAULT_START).unwrap();
+
+            assert_eq!(
+                keyboard.clone().code_extended(&parse_extended(input)),
+                keyboard.clone().code(&parse(input))
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_parse_extended_rejects_garbage() {
+        parse_extended("UL(D");
+    }
+
+    fn random_moves(rng: &mut Lcg, lines: usize, moves: usize) -> String {
+        (0..lines)
+            .map(|_| {
+                let mut line: String = (0..moves)
+                    .map(|_| *rng.choose(&['U', 'D', 'L', 'R']))
+                    .collect();
+                line.push('\n');
+                line
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_execute_bytes() {
+        let mut square = Keyboard::from_layout(SQUARE, DEFAULT_START)
+            .unwrap()
+            .compile();
+        assert_eq!(
+            square.execute_bytes(b"ULL\r\nRRDDD\n\nLURDL\nUUUUD"),
+            Ok("1985".to_string())
+        );
+
+        let mut diamond = Keyboard::from_layout(DIAMOND, DEFAULT_START)
+            .unwrap()
+            .compile();
+        assert_eq!(
+            diamond.execute_bytes(b"ULL\nRRDDD\nLURDL\nUUUUD\n"),
+            Ok("5DB3".to_string())
+        );
+
+        assert_eq!(
+            diamond.execute_bytes(b"UU\nUx"),
+            Err(InvalidByte {
+                offset: 4,
+                byte: b'x'
+            })
+        );
+
+        let mut rng = Lcg::new(43);
+        for layout in [SQUARE, DIAMOND, "ab\nc d", "a"] {
+            let keyboard = Keyboard::from_layout(layout, 'a')
+                .or_else(|_| Keyboard::from_layout(layout, DEFAULT_START))
+                .unwrap();
+            let input = random_moves(&mut rng, 50, 20);
+
+            assert_eq!(
+                keyboard.compile().execute_bytes(input.as_bytes()).unwrap(),
+                keyboard.clone().code(&parse(&input))
+            );
+        }
+    }
+
+    // Run with `cargo test --release -- --ignored --nocapture` to compare.
+    #[test]
+    #[ignore]
+    fn bench_execute_bytes() {
+        use std::time::Instant;
+
+        let input = random_moves(&mut Lcg::new(2016), 10_000, 1_000);
+        let keyboard = Keyboard::from_layout(DIAMOND, DEFAULT_START).unwrap();
+
+        let start = Instant::now();
+        let expected = keyboard.clone().code(&parse(&input));
+        let parsed = start.elapsed();
+
+        let start = Instant::now();
+        let code = keyboard.compile().execute_bytes(input.as_bytes()).unwrap();
+        let compiled = start.elapsed();
+
+        assert_eq!(code, expected);
+        println!(
+            "{} moves: parse and code {:.2?}, compiled table {:.2?}",
+            10_000 * 1_000,
+            parsed,
+            compiled
+        );
+    }
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(
+            parse_line("ULDR"),
+            Ok((
+                "",
+                vec![
+                    Instruction::Up,
+                    Instruction::Left,
+                    Instruction::Down,
+                    Instruction::Right
+                ]
+            ))
+        );
+    }
+}
@@ -0,0 +1,32 @@
+use super::assembunny::{parse_program, Machine};
+
+pub fn solve(input: &str) -> Vec<String> {
+    let program = parse_program(input);
+
+    let mut machine = Machine::new(program.clone());
+    machine.registers[0] = 7;
+    machine.run();
+
+    let part1 = machine.registers[0];
+
+    let mut machine = Machine::new(program);
+    machine.registers[0] = 12;
+    machine.run();
+
+    vec![part1.to_string(), machine.registers[0].to_string()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        let mut machine = Machine::new(parse_program(
+            "cpy 2 a\ntgl a\ntgl a\ntgl a\ncpy 1 a\ndec a\ndec a",
+        ));
+        machine.run();
+
+        assert_eq!(machine.registers[0], 3);
+    }
+}
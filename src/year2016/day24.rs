@@ -0,0 +1,215 @@
+use crate::search::{self, State};
+
+pub fn solve(input: &str) -> Vec<String> {
+    let maze = Maze::from(input);
+    let distances = maze.distances();
+
+    vec![
+        shortest_tour(&distances, false).unwrap().to_string(),
+        shortest_tour(&distances, true).unwrap().to_string(),
+    ]
+}
+
+struct Maze {
+    walls: Vec<Vec<bool>>,
+    points: Vec<(usize, usize)>,
+}
+
+impl Maze {
+    fn from(input: &str) -> Self {
+        let mut points: Vec<(usize, (usize, usize))> = vec![];
+
+        let walls = input
+            .lines()
+            .enumerate()
+            .map(|(y, line)| {
+                line.char_indices()
+                    .map(|(x, c)| {
+                        if let Some(n) = c.to_digit(10) {
+                            points.push((n as usize, (x, y)));
+                        }
+                        c == '#'
+                    })
+                    .collect()
+            })
+            .collect();
+
+        points.sort();
+
+        Maze {
+            walls,
+            points: points.into_iter().map(|(_, p)| p).collect(),
+        }
+    }
+
+    fn is_open(&self, (x, y): (usize, usize)) -> bool {
+        self.walls
+            .get(y)
+            .and_then(|row| row.get(x))
+            .is_some_and(|wall| !wall)
+    }
+
+    fn distances(&self) -> Vec<Vec<u32>> {
+        let points: Vec<Position> = self
+            .points
+            .iter()
+            .map(|&at| Position { maze: self, at })
+            .collect();
+
+        distance_matrix(&points)
+    }
+}
+
+/// Marks a pair of points with no path between them in a distance matrix.
+pub const UNREACHABLE: u32 = u32::MAX;
+
+/// Pairwise step counts between `points`, by a breadth-first search from
+/// each one. Pairs with no path between them are `UNREACHABLE`.
+pub fn distance_matrix<S: State>(points: &[S]) -> Vec<Vec<u32>> {
+    points
+        .iter()
+        .map(|start| {
+            let reached = search::bfs_distances(start.clone());
+
+            points
+                .iter()
+                .map(|p| reached.get(&p.key()).copied().unwrap_or(UNREACHABLE))
+                .collect()
+        })
+        .collect()
+}
+
+#[derive(Clone)]
+struct Position<'a> {
+    maze: &'a Maze,
+    at: (usize, usize),
+}
+
+impl State for Position<'_> {
+    type Key = (usize, usize);
+
+    fn successors(&self) -> Vec<(Self, u32)> {
+        let (x, y) = self.at;
+
+        [
+            (x + 1, y),
+            (x, y + 1),
+            (x.wrapping_sub(1), y),
+            (x, y.wrapping_sub(1)),
+        ]
+        .into_iter()
+        .filter(|&next| self.maze.is_open(next))
+        .map(|at| (Position { at, ..*self }, 1))
+        .collect()
+    }
+
+    fn key(&self) -> Self::Key {
+        self.at
+    }
+}
+
+/// Held-Karp over a distance matrix, starting from point 0. Unreachable pairs
+/// are marked with `UNREACHABLE`; returns `None` if no tour visits every
+/// point.
+pub fn shortest_tour(distances: &[Vec<u32>], return_to_start: bool) -> Option<u32> {
+    let n = distances.len();
+    if n == 0 {
+        return None;
+    }
+
+    // best[mask][j]: shortest path from 0 through the points in `mask`, ending at j
+    let mut best = vec![vec![UNREACHABLE; n]; 1 << n];
+    best[1][0] = 0;
+
+    for mask in (1..1usize << n).filter(|m| m & 1 == 1) {
+        for last in (0..n).filter(|j| mask & (1 << j) != 0) {
+            let length = best[mask][last];
+            if length == UNREACHABLE {
+                continue;
+            }
+
+            for next in (0..n).filter(|j| mask & (1 << j) == 0) {
+                let step = distances[last][next];
+                if step == UNREACHABLE {
+                    continue;
+                }
+
+                let entry = &mut best[mask | (1 << next)][next];
+                *entry = (*entry).min(length + step);
+            }
+        }
+    }
+
+    best[(1 << n) - 1]
+        .iter()
+        .enumerate()
+        .filter(|(_, &length)| length != UNREACHABLE)
+        .filter_map(|(last, &length)| {
+            if return_to_start {
+                let back = distances[last][0];
+                (back != UNREACHABLE).then(|| length + back)
+            } else {
+                Some(length)
+            }
+        })
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "###########
+#0.1.....2#
+#.#######.#
+#4.......3#
+###########";
+
+    #[test]
+    fn test_distances() {
+        let maze = Maze::from(EXAMPLE);
+
+        assert_eq!(maze.points, vec![(1, 1), (3, 1), (9, 1), (9, 3), (1, 3)]);
+        assert_eq!(maze.distances()[0], vec![0, 2, 8, 10, 2]);
+    }
+
+    #[test]
+    fn test_shortest_tour() {
+        let distances = Maze::from(EXAMPLE).distances();
+
+        assert_eq!(shortest_tour(&distances, false), Some(14));
+        assert_eq!(shortest_tour(&distances, true), Some(20));
+    }
+
+    #[test]
+    fn test_shortest_tour_matrix() {
+        let distances = vec![
+            vec![0, 1, 10, UNREACHABLE],
+            vec![1, 0, 2, 7],
+            vec![10, 2, 0, 3],
+            vec![UNREACHABLE, 7, 3, 0],
+        ];
+
+        assert_eq!(shortest_tour(&distances, false), Some(6));
+        assert_eq!(shortest_tour(&distances, true), Some(21));
+        assert_eq!(
+            shortest_tour(&[vec![0, UNREACHABLE], vec![UNREACHABLE, 0]], false),
+            None
+        );
+    }
+
+    #[test]
+    fn test_distance_matrix() {
+        let maze = Maze::from("#####\n#0#1#\n#.#.#\n#####");
+        let points: Vec<Position> = maze
+            .points
+            .iter()
+            .map(|&at| Position { maze: &maze, at })
+            .collect();
+
+        assert_eq!(
+            distance_matrix(&points),
+            vec![vec![0, UNREACHABLE], vec![UNREACHABLE, 0]]
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use std::collections::HashSet;
+
+use super::assembunny::{parse_program, Instruction, Machine, Output};
+
+pub fn solve(input: &str) -> Vec<String> {
+    let program = parse_program(input);
+
+    let a = (0..).find(|&a| is_clock_signal(&program, a)).unwrap();
+
+    vec![a.to_string()]
+}
+
+// The signal is accepted once the machine comes back to a state it was in
+// after an earlier output with the same expected next value: from there on
+// it can only repeat the 0, 1, 0, 1... it has already produced.
+fn is_clock_signal(program: &[Instruction], a: i64) -> bool {
+    let mut machine = Machine::new(program.to_vec());
+    machine.registers[0] = a;
+
+    let mut seen = HashSet::new();
+    let mut expected = 0;
+
+    loop {
+        if machine.next_output() != Output::Value(expected) {
+            return false;
+        }
+
+        expected = 1 - expected;
+
+        if !seen.insert((machine.snapshot(), expected)) {
+            return true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_clock_signal() {
+        let program = parse_program("out 0\nout 1\njnz 1 -2");
+        assert!(is_clock_signal(&program, 0));
+
+        let program = parse_program("out 1\nout 0\njnz 1 -2");
+        assert!(!is_clock_signal(&program, 0));
+
+        let program = parse_program("out 0\nout 1\nout 0\nout 1");
+        assert!(!is_clock_signal(&program, 0));
+
+        // Spins forever after the first pair of outputs.
+        let program = parse_program("out 0\nout 1\njnz 1 0");
+        assert!(!is_clock_signal(&program, 0));
+    }
+
+    #[test]
+    fn test_smallest_a() {
+        let program = parse_program("cpy a c\ndec c\ndec c\nout c\ninc c\nout c\ndec c\njnz 1 -4");
+
+        assert_eq!((0..).find(|&a| is_clock_signal(&program, a)), Some(2));
+    }
+}
@@ -0,0 +1,798 @@
+use std::{
+    collections::VecDeque,
+    fmt,
+    io::{self, BufRead},
+};
+
+use nom::{
+    character::complete::{space0, space1, u32},
+    multi::separated_list0,
+    IResult,
+};
+
+pub fn solve(input: &str) -> Vec<String> {
+    let counts = count(input.as_bytes()).unwrap_or_else(|e| panic!("{}", e));
+
+    vec![counts.by_rows.to_string(), counts.by_columns.to_string()]
+}
+
+/// Possible triangles when reading sides along rows and down columns.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Counts {
+    pub by_rows: usize,
+    pub by_columns: usize,
+}
+
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    /// A line, numbered from 1, that isn't a list of numbers.
+    Parse {
+        line: usize,
+    },
+    Columns(ColumnError),
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StreamError::Io(e) => write!(f, "{}", e),
+            StreamError::Parse { line } => write!(f, "line {} isn't a list of numbers", line),
+            StreamError::Columns(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for StreamError {}
+
+impl From<io::Error> for StreamError {
+    fn from(e: io::Error) -> Self {
+        StreamError::Io(e)
+    }
+}
+
+impl From<ColumnError> for StreamError {
+    fn from(e: ColumnError) -> Self {
+        StreamError::Columns(e)
+    }
+}
+
+/// Counts both parts in one pass over `reader`, holding no more than one
+/// block of three rows at a time. Blank lines are skipped.
+pub fn count(reader: impl BufRead) -> Result<Counts, StreamError> {
+    let mut error = None;
+    let mut by_rows = 0;
+
+    let rows = numbered_rows(reader, &mut error)
+        .map(|(_, row)| row)
+        .inspect(|row| {
+            if is_valid_triangle(row) {
+                by_rows += 1;
+            }
+        });
+
+    let by_columns = ColumnGroups::new(rows, 3, 3).try_fold(0, |count, group| {
+        group.map(|l| count + is_valid_triangle(&l) as usize)
+    });
+
+    // A bad line ends the rows early, which also leaves a group unfinished.
+    if let Some(e) = error {
+        return Err(e);
+    }
+
+    Ok(Counts {
+        by_rows,
+        by_columns: by_columns?,
+    })
+}
+
+/// How the sides of each triangle are laid out in a listing.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Reading {
+    Rows,
+    /// Three rows at a time, one triangle down each column.
+    Columns,
+}
+
+/// A triangle that can't be drawn, and where it came from.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Invalid {
+    /// Line numbers of the sides, from 1.
+    pub lines: Vec<usize>,
+    /// Column of the sides, from 1, when reading by columns.
+    pub column: Option<usize>,
+    pub sides: [u32; 3],
+}
+
+impl Invalid {
+    /// The triangle inequality it breaks, like `5 + 10 < 25`, or `3 + 4 = 7`
+    /// when the triangle is flat.
+    pub fn inequality(&self) -> String {
+        let mut sides = self.sides;
+        sides.sort();
+        let [a, b, c] = sides;
+
+        let relation = if a as u64 + b as u64 == c as u64 {
+            "="
+        } else {
+            "<"
+        };
+
+        format!("{} + {} {} {}", a, b, relation, c)
+    }
+}
+
+impl fmt::Display for Invalid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let lines: Vec<String> = self.lines.iter().map(usize::to_string).collect();
+        let plural = if lines.len() == 1 { "" } else { "s" };
+
+        write!(f, "line{} {}", plural, lines.join(", "))?;
+        if let Some(column) = self.column {
+            write!(f, ", column {}", column)?;
+        }
+        write!(f, ": {}", self.inequality())
+    }
+}
+
+/// Every triangle in the listing that can't be drawn, in the order they
+/// appear.
+pub fn invalid_triangles(
+    reader: impl BufRead,
+    reading: Reading,
+) -> Result<Vec<Invalid>, StreamError> {
+    let mut error = None;
+
+    // Tagging every side with its line lets it ride along through the
+    // regrouping.
+    let rows = numbered_rows(reader, &mut error)
+        .map(|(line, row)| row.into_iter().map(|side| (line, side)).collect());
+
+    let triangles: Box<dyn Iterator<Item = Result<Vec<_>, ColumnError>>> = match reading {
+        Reading::Rows => Box::new(rows.map(Ok)),
+        Reading::Columns => Box::new(ColumnGroups::new(rows, 3, 3)),
+    };
+
+    let invalid: Result<Vec<Invalid>, ColumnError> = triangles
+        .enumerate()
+        .filter_map(|(n, triangle)| {
+            let triangle: Vec<(usize, u32)> = match triangle {
+                Ok(triangle) => triangle,
+                Err(e) => return Some(Err(e)),
+            };
+            let lengths: Vec<u32> = triangle.iter().map(|&(_, side)| side).collect();
+
+            (!is_valid_triangle(&lengths)).then(|| {
+                let mut lines: Vec<usize> = triangle.iter().map(|&(line, _)| line).collect();
+                lines.dedup();
+
+                Ok(Invalid {
+                    lines,
+                    column: (reading == Reading::Columns).then_some(n % 3 + 1),
+                    sides: sides(&lengths),
+                })
+            })
+        })
+        .collect();
+
+    if let Some(e) = error {
+        return Err(e);
+    }
+
+    Ok(invalid?)
+}
+
+/// Writes the invalid triangles as CSV, with the line numbers of a
+/// triangle separated by spaces.
+pub fn to_csv(invalid: &[Invalid]) -> String {
+    let mut csv = String::from("lines,column,a,b,c,inequality\n");
+
+    for triangle in invalid {
+        let lines: Vec<String> = triangle.lines.iter().map(usize::to_string).collect();
+        let [a, b, c] = triangle.sides;
+
+        csv += &format!(
+            "{},{},{},{},{},{}\n",
+            lines.join(" "),
+            triangle.column.map_or(String::new(), |c| c.to_string()),
+            a,
+            b,
+            c,
+            triangle.inequality()
+        );
+    }
+
+    csv
+}
+
+// Numbered rows of three numbers, skipping blank lines. Stops at the first
+// line that can't be read or parsed or has the wrong width, leaving the
+// reason in `error`.
+fn numbered_rows<'a>(
+    mut reader: impl BufRead + 'a,
+    error: &'a mut Option<StreamError>,
+) -> impl Iterator<Item = (usize, Vec<u32>)> + 'a {
+    let mut buffer = String::new();
+    let mut line = 0;
+
+    std::iter::from_fn(move || loop {
+        buffer.clear();
+        line += 1;
+
+        match reader.read_line(&mut buffer) {
+            Ok(0) => return None,
+            Ok(_) if buffer.trim().is_empty() => continue,
+            Ok(_) => {
+                return match parse_line(&buffer) {
+                    Ok((rest, row)) if rest.trim().is_empty() && row.len() != 3 => {
+                        Some(Err(StreamError::Columns(ColumnError::WrongWidth {
+                            row: line,
+                            expected: 3,
+                            found: row.len(),
+                        })))
+                    }
+                    Ok((rest, row)) if rest.trim().is_empty() => Some(Ok((line, row))),
+                    _ => Some(Err(StreamError::Parse { line })),
+                }
+            }
+            Err(e) => return Some(Err(StreamError::Io(e))),
+        }
+    })
+    .map_while(|row| row.map_err(|e| *error = Some(e)).ok())
+}
+
+/// Classifies the triangles read by rows and by columns, in one pass over
+/// `reader` like `count`.
+pub fn report(reader: impl BufRead) -> Result<(Report, Report), StreamError> {
+    let mut error = None;
+    let mut by_rows = Report::default();
+
+    let rows = numbered_rows(reader, &mut error)
+        .map(|(_, row)| row)
+        .inspect(|row| by_rows.add(Triangle::classify(sides(row))));
+
+    let by_columns: Result<Report, ColumnError> = ColumnGroups::new(rows, 3, 3)
+        .map(|group| group.map(|l| Triangle::classify(sides(&l))))
+        .collect();
+
+    if let Some(e) = error {
+        return Err(e);
+    }
+
+    Ok((by_rows, by_columns?))
+}
+
+fn sides(l: &[u32]) -> [u32; 3] {
+    l.try_into()
+        .unwrap_or_else(|_| panic!("a triangle has 3 sides, not {}", l.len()))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ColumnError {
+    /// A row, numbered from 1, doesn't have the expected number of columns.
+    /// Rows read from a listing are numbered by their line instead.
+    WrongWidth {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The rows ran out partway through a group.
+    IncompleteGroup { rows: usize, expected: usize },
+}
+
+impl fmt::Display for ColumnError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ColumnError::WrongWidth {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {} has {} columns instead of {}",
+                row, found, expected
+            ),
+            ColumnError::IncompleteGroup { rows, expected } => write!(
+                f,
+                "the last group has {} rows instead of {}",
+                rows, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ColumnError {}
+
+/// Reads rows in blocks of `group` and yields each column of a block in
+/// turn, so every `group` numbers down a column become one item. Stops after
+/// the first error.
+pub struct ColumnGroups<I, T> {
+    rows: I,
+    group: usize,
+    columns: usize,
+    row: usize,
+    pending: VecDeque<Vec<T>>,
+    failed: bool,
+}
+
+impl<I: Iterator<Item = Vec<T>>, T> ColumnGroups<I, T> {
+    pub fn new(rows: impl IntoIterator<IntoIter = I>, group: usize, columns: usize) -> Self {
+        assert!(group > 0, "groups need at least one row");
+
+        ColumnGroups {
+            rows: rows.into_iter(),
+            group,
+            columns,
+            row: 0,
+            pending: VecDeque::new(),
+            failed: false,
+        }
+    }
+
+    fn read_block(&mut self) -> Option<Result<(), ColumnError>> {
+        let mut block = (0..self.columns).map(|_| vec![]).collect::<Vec<_>>();
+
+        for read in 0..self.group {
+            let Some(row) = self.rows.next() else {
+                return (read > 0).then_some(Err(ColumnError::IncompleteGroup {
+                    rows: read,
+                    expected: self.group,
+                }));
+            };
+            self.row += 1;
+
+            if row.len() != self.columns {
+                return Some(Err(ColumnError::WrongWidth {
+                    row: self.row,
+                    expected: self.columns,
+                    found: row.len(),
+                }));
+            }
+
+            for (column, value) in block.iter_mut().zip(row) {
+                column.push(value);
+            }
+        }
+
+        self.pending.extend(block);
+        Some(Ok(()))
+    }
+}
+
+impl<I: Iterator<Item = Vec<T>>, T> Iterator for ColumnGroups<I, T> {
+    type Item = Result<Vec<T>, ColumnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() {
+            if self.failed {
+                return None;
+            }
+
+            if let Err(e) = self.read_block()? {
+                self.failed = true;
+                return Some(Err(e));
+            }
+        }
+
+        self.pending.pop_front().map(Ok)
+    }
+}
+
+fn is_valid_triangle(l: &[u32]) -> bool {
+    l.len() == 3 && is_valid_polygon(l)
+}
+
+/// Whether the sides can close into a polygon with some area: at least
+/// three of them, with the longest shorter than all the others together.
+pub fn is_valid_polygon(sides: &[u32]) -> bool {
+    let longest = sides.iter().max().copied().unwrap_or(0) as u64;
+    let total: u64 = sides.iter().map(|&side| side as u64).sum();
+
+    sides.len() >= 3 && longest < total - longest
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Sides {
+    Equilateral,
+    Isosceles,
+    Scalene,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Angle {
+    Acute,
+    Right,
+    Obtuse,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Triangle {
+    /// The longest side is longer than the other two together.
+    Impossible,
+    /// The longest side is exactly as long as the other two, so the corners
+    /// lie on a line.
+    Degenerate,
+    Proper {
+        sides: Sides,
+        angle: Angle,
+    },
+}
+
+impl Triangle {
+    pub fn classify(mut sides: [u32; 3]) -> Self {
+        sides.sort();
+        let [a, b, c] = sides.map(|side| side as u128);
+
+        if a + b < c {
+            return Triangle::Impossible;
+        }
+
+        if a + b == c {
+            return Triangle::Degenerate;
+        }
+
+        let sides = if a == c {
+            Sides::Equilateral
+        } else if a == b || b == c {
+            Sides::Isosceles
+        } else {
+            Sides::Scalene
+        };
+
+        let angle = match (a * a + b * b).cmp(&(c * c)) {
+            std::cmp::Ordering::Greater => Angle::Acute,
+            std::cmp::Ordering::Equal => Angle::Right,
+            std::cmp::Ordering::Less => Angle::Obtuse,
+        };
+
+        Triangle::Proper { sides, angle }
+    }
+}
+
+/// How many triangles of each kind a listing has. Only proper triangles
+/// are counted by their sides and angles.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct Report {
+    pub total: usize,
+    pub impossible: usize,
+    pub degenerate: usize,
+    pub equilateral: usize,
+    pub isosceles: usize,
+    pub scalene: usize,
+    pub acute: usize,
+    pub right: usize,
+    pub obtuse: usize,
+}
+
+impl Report {
+    pub fn add(&mut self, triangle: Triangle) {
+        self.total += 1;
+
+        match triangle {
+            Triangle::Impossible => self.impossible += 1,
+            Triangle::Degenerate => self.degenerate += 1,
+            Triangle::Proper { sides, angle } => {
+                match sides {
+                    Sides::Equilateral => self.equilateral += 1,
+                    Sides::Isosceles => self.isosceles += 1,
+                    Sides::Scalene => self.scalene += 1,
+                }
+
+                match angle {
+                    Angle::Acute => self.acute += 1,
+                    Angle::Right => self.right += 1,
+                    Angle::Obtuse => self.obtuse += 1,
+                }
+            }
+        }
+    }
+
+    /// Triangles with some area, which is what both parts count.
+    pub fn possible(&self) -> usize {
+        self.total - self.impossible - self.degenerate
+    }
+}
+
+impl FromIterator<Triangle> for Report {
+    fn from_iter<I: IntoIterator<Item = Triangle>>(iter: I) -> Self {
+        let mut report = Report::default();
+        iter.into_iter().for_each(|triangle| report.add(triangle));
+        report
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Triangles:   {}", self.total)?;
+        writeln!(f, "Possible:    {}", self.possible())?;
+        writeln!(f, "Impossible:  {}", self.impossible)?;
+        writeln!(f, "Degenerate:  {}", self.degenerate)?;
+        writeln!(f, "Equilateral: {}", self.equilateral)?;
+        writeln!(f, "Isosceles:   {}", self.isosceles)?;
+        writeln!(f, "Scalene:     {}", self.scalene)?;
+        writeln!(f, "Acute:       {}", self.acute)?;
+        writeln!(f, "Right:       {}", self.right)?;
+        writeln!(f, "Obtuse:      {}", self.obtuse)
+    }
+}
+
+fn parse_line(input: &str) -> IResult<&str, Vec<u32>> {
+    let (input, _) = space0(input)?;
+    separated_list0(space1, u32)(input)
+}
+
+// Now that you've helpfully marked up their design documents, it occurs to you that triangles are specified in groups of three vertically.
+// Each set of three numbers in a column specifies a triangle. Rows are unrelated.
+
+// For example, given the following specification, numbers with the same hundreds digit would be part of the same triangle:
+
+// 101 301 501
+// 102 302 502
+// 103 303 503
+// 201 401 601
+// 202 402 602
+// 203 403 603
+
+// In your puzzle input, and instead reading by columns, how many of the listed triangles are possible?
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Lcg;
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(parse_line(" 2  628  436"), Ok(("", vec![2, 628, 436])));
+    }
+
+    #[test]
+    fn test_column_groups() {
+        let rows = vec![
+            vec![101, 301, 501],
+            vec![102, 302, 502],
+            vec![103, 303, 503],
+            vec![201, 401, 601],
+            vec![202, 402, 602],
+            vec![203, 403, 603],
+        ];
+
+        let groups: Vec<Vec<u32>> = ColumnGroups::new(rows, 3, 3).map(Result::unwrap).collect();
+        assert_eq!(groups.len(), 6);
+        assert_eq!(groups[0], vec![101, 102, 103]);
+        assert_eq!(groups[2], vec![501, 502, 503]);
+        assert_eq!(groups[3], vec![201, 202, 203]);
+
+        let pairs: Vec<Vec<char>> = ColumnGroups::new(vec![vec!['a', 'b'], vec!['c', 'd']], 2, 2)
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(pairs, vec![vec!['a', 'c'], vec!['b', 'd']]);
+
+        assert_eq!(ColumnGroups::new(Vec::<Vec<u32>>::new(), 3, 3).count(), 0);
+    }
+
+    #[test]
+    fn test_column_groups_ragged() {
+        let short_row = vec![vec![1, 2, 3], vec![4, 5], vec![7, 8, 9]];
+        let groups: Vec<_> = ColumnGroups::new(short_row, 3, 3).collect();
+        assert_eq!(
+            groups,
+            vec![Err(ColumnError::WrongWidth {
+                row: 2,
+                expected: 3,
+                found: 2
+            })]
+        );
+
+        let extra_rows = vec![vec![1, 2], vec![3, 4], vec![5, 6]];
+        let groups: Vec<_> = ColumnGroups::new(extra_rows, 2, 2).collect();
+        assert_eq!(
+            groups,
+            vec![
+                Ok(vec![1, 3]),
+                Ok(vec![2, 4]),
+                Err(ColumnError::IncompleteGroup {
+                    rows: 1,
+                    expected: 2
+                })
+            ]
+        );
+    }
+
+    // Produces rows of a listing on demand, without ever holding all of it.
+    struct Generated {
+        rng: Lcg,
+        rows: usize,
+        pending: Vec<u8>,
+    }
+
+    impl io::Read for Generated {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.pending.is_empty() && self.rows > 0 {
+                self.rows -= 1;
+                let sides: Vec<String> = (0..3).map(|_| self.rng.below(1000).to_string()).collect();
+                self.pending = format!("  {}\n", sides.join("  ")).into_bytes();
+            }
+
+            let n = buf.len().min(self.pending.len());
+            buf[..n].copy_from_slice(&self.pending[..n]);
+            self.pending.drain(..n);
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_count() {
+        let input =
+            "101 301 501\n102 302 502\n103 303 503\n\n201 401 601\n202 402 602\n203 403 603";
+        assert_eq!(
+            count(input.as_bytes()).unwrap(),
+            Counts {
+                by_rows: 3,
+                by_columns: 6
+            }
+        );
+
+        let generated = || Generated {
+            rng: Lcg::new(3),
+            rows: 3000,
+            pending: vec![],
+        };
+        let (by_rows, by_columns) = report(io::BufReader::new(generated())).unwrap();
+
+        assert_eq!(
+            count(io::BufReader::new(generated())).unwrap(),
+            Counts {
+                by_rows: by_rows.possible(),
+                by_columns: by_columns.possible()
+            }
+        );
+    }
+
+    #[test]
+    fn test_count_errors() {
+        assert!(matches!(
+            count("1 2 3\n4 five 6\n7 8 9\n".as_bytes()),
+            Err(StreamError::Parse { line: 2 })
+        ));
+        assert!(matches!(
+            count("1 2 3\n4 5 6\n".as_bytes()),
+            Err(StreamError::Columns(ColumnError::IncompleteGroup {
+                rows: 2,
+                expected: 3
+            }))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_triangles() {
+        let listing = "5 10 25\n3 4 5\n\n7 3 7\n";
+
+        let by_rows = invalid_triangles(listing.as_bytes(), Reading::Rows).unwrap();
+        assert_eq!(
+            by_rows,
+            vec![Invalid {
+                lines: vec![1],
+                column: None,
+                sides: [5, 10, 25]
+            }]
+        );
+        assert_eq!(by_rows[0].to_string(), "line 1: 5 + 10 < 25");
+
+        let by_columns = invalid_triangles(listing.as_bytes(), Reading::Columns).unwrap();
+        assert_eq!(
+            by_columns,
+            vec![
+                Invalid {
+                    lines: vec![1, 2, 4],
+                    column: Some(2),
+                    sides: [10, 4, 3]
+                },
+                Invalid {
+                    lines: vec![1, 2, 4],
+                    column: Some(3),
+                    sides: [25, 5, 7]
+                }
+            ]
+        );
+        assert_eq!(
+            by_columns[0].to_string(),
+            "lines 1, 2, 4, column 2: 3 + 4 < 10"
+        );
+        assert_eq!(
+            to_csv(&by_columns),
+            "lines,column,a,b,c,inequality\n1 2 4,2,10,4,3,3 + 4 < 10\n1 2 4,3,25,5,7,5 + 7 < 25\n"
+        );
+    }
+
+    #[test]
+    fn test_invalid_triangles_errors() {
+        assert!(matches!(
+            invalid_triangles("1 2 3\n1 2\n".as_bytes(), Reading::Rows),
+            Err(StreamError::Columns(ColumnError::WrongWidth { row: 2, .. }))
+        ));
+        assert!(matches!(
+            invalid_triangles("1 2 3\n\n1 2\n".as_bytes(), Reading::Columns),
+            Err(StreamError::Columns(ColumnError::WrongWidth { row: 3, .. }))
+        ));
+        assert!(matches!(
+            count("\n1 2 3\n4 5 6 7\n".as_bytes()),
+            Err(StreamError::Columns(ColumnError::WrongWidth { row: 3, .. }))
+        ));
+        assert!(matches!(
+            invalid_triangles("1 2 3\n1 x 2\n".as_bytes(), Reading::Columns),
+            Err(StreamError::Parse { line: 2 })
+        ));
+        assert_eq!(
+            invalid_triangles("3 4 7".as_bytes(), Reading::Rows).unwrap()[0].inequality(),
+            "3 + 4 = 7"
+        );
+    }
+
+    #[test]
+    fn test_is_valid_triangle() {
+        assert!(is_valid_triangle(&vec![10, 10, 10]));
+        assert!(!is_valid_triangle(&vec![1, 1, 10]));
+        assert!(!is_valid_triangle(&[u32::MAX, u32::MAX, 1, 1]));
+        assert!(is_valid_triangle(&[u32::MAX, u32::MAX, 1]));
+    }
+
+    #[test]
+    fn test_is_valid_polygon() {
+        assert!(is_valid_polygon(&[1, 1, 1, 2]));
+        assert!(!is_valid_polygon(&[1, 1, 1, 3]));
+        assert!(is_valid_polygon(&[u32::MAX, u32::MAX, u32::MAX, u32::MAX]));
+        assert!(!is_valid_polygon(&[5, 5]));
+        assert!(!is_valid_polygon(&[]));
+    }
+
+    #[test]
+    fn test_classify() {
+        let proper = |sides, angle| Triangle::Proper { sides, angle };
+
+        assert_eq!(Triangle::classify([5, 10, 25]), Triangle::Impossible);
+        assert_eq!(Triangle::classify([3, 7, 4]), Triangle::Degenerate);
+        assert_eq!(
+            Triangle::classify([7, 7, 7]),
+            proper(Sides::Equilateral, Angle::Acute)
+        );
+        assert_eq!(
+            Triangle::classify([5, 3, 4]),
+            proper(Sides::Scalene, Angle::Right)
+        );
+        assert_eq!(
+            Triangle::classify([2, 2, 3]),
+            proper(Sides::Isosceles, Angle::Obtuse)
+        );
+        assert_eq!(
+            Triangle::classify([u32::MAX, u32::MAX, u32::MAX - 1]),
+            proper(Sides::Isosceles, Angle::Acute)
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let (by_rows, by_columns) = report("5 10 25\n3 4 5\n 7  7  7\n".as_bytes()).unwrap();
+
+        assert_eq!(by_rows.total, 3);
+        assert_eq!(by_rows.impossible, 1);
+        assert_eq!(by_rows.possible(), 2);
+        assert_eq!(by_rows.right, 1);
+        assert_eq!(by_rows.equilateral, 1);
+
+        // Columns 5 3 7, 10 4 7 and 25 5 7.
+        assert_eq!(by_columns.scalene, 2);
+        assert_eq!(by_columns.impossible, 1);
+        assert_eq!(by_columns.obtuse, 2);
+
+        assert!(matches!(
+            report("1 2 3\n\n1 x 3\n".as_bytes()),
+            Err(StreamError::Parse { line: 3 })
+        ));
+        assert!(matches!(
+            report("1 2 3\n4 5 6\n".as_bytes()),
+            Err(StreamError::Columns(ColumnError::IncompleteGroup {
+                rows: 2,
+                ..
+            }))
+        ));
+    }
+}
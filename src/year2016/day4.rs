@@ -0,0 +1,627 @@
+use std::{cmp::Ordering, collections::HashMap, fmt, str::FromStr};
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, u32},
+    combinator::all_consuming,
+    multi::separated_list1,
+    sequence::{delimited, preceded},
+    IResult,
+};
+use regex::Regex;
+
+use crate::rng::Lcg;
+
+pub fn solve(input: &str) -> Vec<String> {
+    let lines: Vec<Line> = input
+        .lines()
+        .map(parse_line)
+        .map(Result::unwrap)
+        .map(|i| i.1)
+        .collect();
+
+    let verified: Vec<&Line> = lines.iter().filter(|&l| verify_line(l)).collect();
+
+    let part1 = verified.iter().map(|l| l.sector).sum::<u32>();
+
+    let query = Query::glob("northpole object storage");
+
+    let part2 = search(input, &query).unwrap().first().unwrap().sector;
+
+    vec![part1.to_string(), part2.to_string()]
+}
+
+/// A real room with its name decrypted.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Decrypted {
+    pub name: String,
+    pub sector: u32,
+}
+
+pub enum Query {
+    Substring(String),
+    /// Matches anywhere in the name, like `grep`.
+    Pattern(Regex),
+}
+
+impl Query {
+    /// A pattern that must match the whole name, where `*` stands for any
+    /// run of characters and `?` for a single one.
+    pub fn glob(pattern: &str) -> Self {
+        let pattern: String = pattern
+            .chars()
+            .map(|c| match c {
+                '*' => ".*".to_string(),
+                '?' => ".".to_string(),
+                _ => regex::escape(&c.to_string()),
+            })
+            .collect();
+
+        Query::Pattern(Regex::new(&format!("^{}$", pattern)).unwrap())
+    }
+
+    pub fn regex(pattern: &str) -> Result<Self, regex::Error> {
+        Regex::new(pattern).map(Query::Pattern)
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            Query::Substring(s) => name.contains(s.as_str()),
+            Query::Pattern(regex) => regex.is_match(name),
+        }
+    }
+}
+
+/// A line of a room listing, numbered from 1, that isn't a room.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ListingError {
+    pub line: usize,
+    pub input: String,
+}
+
+impl fmt::Display for ListingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}: {:?} isn't a room listing",
+            self.line, self.input
+        )
+    }
+}
+
+impl std::error::Error for ListingError {}
+
+/// Every real room, decrypted and sorted by sector.
+pub fn directory(input: &str) -> Result<Vec<Decrypted>, ListingError> {
+    let mut rooms = vec![];
+
+    for (n, text) in input.lines().enumerate() {
+        let (_, l) = all_consuming(parse_line)(text).map_err(|_| ListingError {
+            line: n + 1,
+            input: text.to_string(),
+        })?;
+
+        if verify_line(&l) {
+            rooms.push(Decrypted {
+                name: rotate_by_sector(&l),
+                sector: l.sector,
+            });
+        }
+    }
+
+    rooms.sort_by_key(|room| room.sector);
+    Ok(rooms)
+}
+
+/// The real rooms whose decrypted name matches, sorted by sector.
+pub fn search(input: &str, query: &Query) -> Result<Vec<Decrypted>, ListingError> {
+    let mut rooms = directory(input)?;
+    rooms.retain(|room| query.matches(&room.name));
+    Ok(rooms)
+}
+
+/// A room listing like `aaaaa-bbb-z-y-x-123[abxyz]` that owns its parts,
+/// and prints back exactly as it was read.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Room {
+    pub encrypted_name: Vec<String>,
+    pub sector: u32,
+    pub checksum: String,
+}
+
+impl Room {
+    /// Whether the checksum is right, rather than a decoy.
+    pub fn is_real(&self) -> bool {
+        verify_line(&self.as_line())
+    }
+
+    pub fn decrypt(&self) -> String {
+        rotate_by_sector(&self.as_line())
+    }
+
+    fn as_line(&self) -> Line<'_> {
+        Line {
+            encoded_name: self.encrypted_name.iter().map(String::as_str).collect(),
+            sector: self.sector,
+            checksum: &self.checksum,
+        }
+    }
+}
+
+impl fmt::Display for Room {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}-{}[{}]",
+            self.encrypted_name.join("-"),
+            self.sector,
+            self.checksum
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseRoomError {
+    pub input: String,
+}
+
+impl fmt::Display for ParseRoomError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} isn't a room listing", self.input)
+    }
+}
+
+impl std::error::Error for ParseRoomError {}
+
+impl FromStr for Room {
+    type Err = ParseRoomError;
+
+    /// Only accepts lowercase listings that print back the same, so
+    /// sectors can't have leading zeros.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseRoomError {
+            input: s.to_string(),
+        };
+
+        let (_, line) = all_consuming(parse_line)(s).map_err(|_| error())?;
+        let room = Room {
+            encrypted_name: line.encoded_name.iter().map(|w| w.to_string()).collect(),
+            sector: line.sector,
+            checksum: line.checksum.to_string(),
+        };
+
+        let lowercase = |w: &str| w.bytes().all(|b| b.is_ascii_lowercase());
+        if !room.encrypted_name.iter().all(|w| lowercase(w))
+            || !lowercase(&room.checksum)
+            || room.to_string() != s
+        {
+            return Err(error());
+        }
+
+        Ok(room)
+    }
+}
+
+impl TryFrom<&str> for Room {
+    type Error = ParseRoomError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Room> for String {
+    fn from(room: Room) -> Self {
+        room.to_string()
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Line<'a> {
+    encoded_name: Vec<&'a str>,
+    sector: u32,
+    checksum: &'a str,
+}
+
+fn parse_line(input: &str) -> IResult<&str, Line> {
+    let (input, name) = separated_list1(tag("-"), alpha1)(input)?;
+    let (input, sector) = preceded(tag("-"), u32)(input)?;
+    let (input, checksum) = delimited(tag("["), alpha1, tag("]"))(input)?;
+
+    Ok((
+        input,
+        Line {
+            encoded_name: name,
+            sector,
+            checksum,
+        },
+    ))
+}
+
+fn verify_line(line: &Line) -> bool {
+    checksum(&line.encoded_name).eq(&line.checksum)
+}
+
+// The five most common letters, ties broken alphabetically.
+fn checksum(encoded_name: &[&str]) -> String {
+    let mut letters: HashMap<char, u32> = HashMap::new();
+
+    encoded_name
+        .join("")
+        .chars()
+        .for_each(|c| match letters.get_mut(&c) {
+            Some(count) => {
+                *count += 1;
+            }
+            None => {
+                letters.insert(c, 1);
+            }
+        });
+
+    let mut counts: Vec<(u32, char)> = letters.iter().map(|(c, num)| (*num, *c)).collect();
+
+    counts.sort_by(|a, b| match a.0.cmp(&b.0) {
+        Ordering::Less => Ordering::Greater,
+        Ordering::Equal => a.1.cmp(&b.1),
+        Ordering::Greater => Ordering::Less,
+    });
+
+    counts.iter().map(|(_, c)| *c).take(5).collect::<String>()
+}
+
+fn rotate_by_sector(l: &Line) -> String {
+    let name = l.encoded_name.join(" ");
+
+    let name = name
+        .bytes()
+        .map(|b| match b {
+            b' ' => ' ',
+            _ => {
+                // Listings may hold uppercase, which wraps instead of panicking.
+                let letter = (b.wrapping_sub(b'a') % 26) as u32;
+                (((letter + l.sector % 26) % 26) as u8 + b'a') as char
+            }
+        })
+        .collect::<String>();
+
+    name
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum EncodeError {
+    /// The name is empty, or has two spaces in a row or at an end.
+    EmptyWord,
+    /// Names are lowercase words separated by spaces.
+    InvalidCharacter(char),
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EncodeError::EmptyWord => write!(f, "the name has an empty word"),
+            EncodeError::InvalidCharacter(c) => write!(f, "{:?} can't be encrypted", c),
+        }
+    }
+}
+
+impl std::error::Error for EncodeError {}
+
+/// Encrypts a room name into a room with a valid checksum, the inverse of
+/// `Room::decrypt`.
+pub fn encode(name: &str, sector: u32) -> Result<Room, EncodeError> {
+    let encrypted_name = encrypt(name, sector)?;
+    let words: Vec<&str> = encrypted_name.iter().map(String::as_str).collect();
+    let checksum = checksum(&words);
+
+    Ok(Room {
+        encrypted_name,
+        sector,
+        checksum,
+    })
+}
+
+/// Like `encode`, but with a checksum that looks real and isn't: five
+/// letters picked from the name, topped up from the alphabet when it has
+/// fewer. The same seed always gives the same decoy.
+pub fn decoy(name: &str, sector: u32, seed: u64) -> Result<Room, EncodeError> {
+    let encrypted_name = encrypt(name, sector)?;
+    let words: Vec<&str> = encrypted_name.iter().map(String::as_str).collect();
+    let real = checksum(&words);
+
+    let mut pool: Vec<char> = vec![];
+    for c in words.concat().chars() {
+        if !pool.contains(&c) {
+            pool.push(c);
+        }
+    }
+    for c in 'a'..='z' {
+        if pool.len() < 5 && !pool.contains(&c) {
+            pool.push(c);
+        }
+    }
+
+    let mut rng = Lcg::new(seed);
+
+    loop {
+        // A partial Fisher-Yates shuffle picks the first five.
+        for i in 0..5 {
+            let j = i + rng.below(pool.len() - i);
+            pool.swap(i, j);
+        }
+
+        let checksum: String = pool[..5].iter().collect();
+        if checksum != real {
+            return Ok(Room {
+                encrypted_name,
+                sector,
+                checksum,
+            });
+        }
+    }
+}
+
+fn encrypt(name: &str, sector: u32) -> Result<Vec<String>, EncodeError> {
+    let shift = 26 - sector % 26;
+
+    name.split(' ')
+        .map(|word| {
+            if word.is_empty() {
+                return Err(EncodeError::EmptyWord);
+            }
+
+            word.chars()
+                .map(|c| match c {
+                    'a'..='z' => Ok(((c as u32 - 97 + shift) % 26 + 97) as u8 as char),
+                    _ => Err(EncodeError::InvalidCharacter(c)),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(
+            parse_line("aaaaa-bbb-z-y-x-123[abxyz]"),
+            Ok((
+                "",
+                Line {
+                    encoded_name: vec!["aaaaa", "bbb", "z", "y", "x"],
+                    sector: 123,
+                    checksum: "abxyz"
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_verify_line() {
+        assert_eq!(
+            verify_line(&Line {
+                encoded_name: vec!["aaaaa", "bbb", "z", "y", "x"],
+                sector: 123,
+                checksum: "abxyz"
+            }),
+            true
+        );
+
+        assert_eq!(
+            verify_line(&parse_line("a-b-c-d-e-f-g-h-987[abcde]").unwrap().1),
+            true
+        );
+
+        assert_eq!(
+            verify_line(&parse_line("not-a-real-room-404[oarel]").unwrap().1),
+            true
+        );
+
+        assert_eq!(
+            verify_line(&parse_line("totally-real-room-200[decoy]").unwrap().1),
+            false
+        );
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(
+            encode("very encrypted name", 343).unwrap().to_string(),
+            "qzmt-zixmtkozy-ivhz-343[zimth]"
+        );
+
+        for (name, sector) in [("northpole object storage", 26), ("a", 1), ("zz top", 700)] {
+            let encoded = encode(name, sector).unwrap();
+            let line = encoded.as_line();
+
+            assert!(verify_line(&line));
+            assert_eq!(rotate_by_sector(&line), name);
+            assert_eq!(line.sector, sector);
+        }
+
+        assert_eq!(encode("", 1), Err(EncodeError::EmptyWord));
+        assert_eq!(encode("two  spaces", 1), Err(EncodeError::EmptyWord));
+        assert_eq!(
+            encode("Capital", 1),
+            Err(EncodeError::InvalidCharacter('C'))
+        );
+    }
+
+    #[test]
+    fn test_decoy() {
+        for seed in 0..50 {
+            for name in ["very encrypted name", "abcde", "aa"] {
+                let decoy = decoy(name, 343, seed).unwrap();
+                let line = decoy.as_line();
+
+                assert!(!verify_line(&line));
+                assert_eq!(line.checksum.len(), 5);
+                assert_eq!(rotate_by_sector(&line), name);
+            }
+        }
+
+        // Decoys reuse the name's letters where it has enough of them.
+        let room = decoy("very encrypted name", 343, 7).unwrap();
+        assert!(room
+            .checksum
+            .chars()
+            .all(|c| room.encrypted_name.concat().contains(c)));
+
+        assert_eq!(decoy("room", 1, 3), decoy("room", 1, 3));
+    }
+
+    fn sectors(rooms: &[Decrypted]) -> Vec<u32> {
+        rooms.iter().map(|room| room.sector).collect()
+    }
+
+    #[test]
+    fn test_search() {
+        let input = [
+            encode("northpole object storage", 500).unwrap(),
+            encode("candy cane storage", 42).unwrap(),
+            decoy("northpole decoy storage", 7, 1).unwrap(),
+            encode("scavenger hunt", 99).unwrap(),
+        ]
+        .map(String::from)
+        .join("\n");
+
+        assert_eq!(
+            directory(&input).unwrap(),
+            vec![
+                Decrypted {
+                    name: "candy cane storage".to_string(),
+                    sector: 42
+                },
+                Decrypted {
+                    name: "scavenger hunt".to_string(),
+                    sector: 99
+                },
+                Decrypted {
+                    name: "northpole object storage".to_string(),
+                    sector: 500
+                },
+            ]
+        );
+
+        let storage = Query::Substring("storage".to_string());
+        assert_eq!(sectors(&search(&input, &storage).unwrap()), vec![42, 500]);
+
+        assert_eq!(
+            sectors(&search(&input, &Query::glob("candy c?ne *")).unwrap()),
+            vec![42]
+        );
+        assert_eq!(
+            sectors(&search(&input, &Query::glob("storage")).unwrap()),
+            vec![]
+        );
+        assert_eq!(
+            sectors(&search(&input, &Query::glob("*.*")).unwrap()),
+            vec![]
+        );
+
+        let query = Query::regex(r"^\w+ (hunt|object)").unwrap();
+        assert_eq!(sectors(&search(&input, &query).unwrap()), vec![99, 500]);
+        assert!(Query::regex("(").is_err());
+
+        assert_eq!(
+            sectors(&directory("A-100[A]\nZzz-32[zZ]").unwrap()),
+            vec![32, 100]
+        );
+        assert_eq!(
+            directory("a-1[a]\ngarbage"),
+            Err(ListingError {
+                line: 2,
+                input: "garbage".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = [
+            encode("northpole object storage annex", 10).unwrap(),
+            encode("northpole object storage", 20).unwrap(),
+        ]
+        .map(String::from)
+        .join("\n");
+
+        assert_eq!(solve(&input), vec!["30", "20"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_solve_without_storage() {
+        solve(
+            &encode("northpole object storage annex", 10)
+                .unwrap()
+                .to_string(),
+        );
+    }
+
+    #[test]
+    fn test_room() {
+        for listing in [
+            "aaaaa-bbb-z-y-x-123[abxyz]",
+            "totally-real-room-200[decoy]",
+            "a-4294967295[a]",
+        ] {
+            let room: Room = listing.parse().unwrap();
+            assert_eq!(room.to_string(), listing);
+            assert_eq!(Room::try_from(listing), Ok(room.clone()));
+            assert_eq!(String::from(room), listing);
+        }
+
+        let room: Room = "qzmt-zixmtkozy-ivhz-343[zimth]".parse().unwrap();
+        assert!(room.is_real());
+        assert_eq!(room.decrypt(), "very encrypted name");
+        assert_eq!(encode("very encrypted name", 343), Ok(room));
+
+        let room: Room = "a-4294967295[a]".parse().unwrap();
+        assert_eq!(room.decrypt(), "v");
+
+        for bad in [
+            "aaaaa-bbb-123",
+            "a-0123[abc]",
+            "a-123[abc] ",
+            "-123[abc]",
+            "a-123[abc]extra",
+            "Mixed-Case-0[ab]",
+            "a-1[A]",
+        ] {
+            assert_eq!(
+                bad.parse::<Room>(),
+                Err(ParseRoomError {
+                    input: bad.to_string()
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_room_ordering() {
+        let mut rooms: Vec<Room> = ["b-1[b]", "a-2[a]", "a-1[a]"]
+            .iter()
+            .map(|listing| listing.parse().unwrap())
+            .collect();
+        rooms.sort();
+
+        let listings: Vec<String> = rooms.into_iter().map(String::from).collect();
+        assert_eq!(listings, vec!["a-1[a]", "a-2[a]", "b-1[b]"]);
+    }
+
+    #[test]
+    fn verify_rotate() {
+        dbg!(b'a' - 97);
+        dbg!(b'z' - b'a');
+        assert_eq!(
+            rotate_by_sector(&Line {
+                encoded_name: vec!["qzmt", "zixmtkozy", "ivhz"],
+                sector: 343,
+                checksum: "abxyz"
+            }),
+            String::from("very encrypted name")
+        );
+    }
+}
@@ -1,4 +1,4 @@
-pub fn solve(input: &str) {
+pub fn solve(input: &str) -> Vec<String> {
     let mut hash1 = String::new();
     let mut hash2 = [None, None, None, None, None, None, None, None];
 
@@ -29,12 +29,7 @@ pub fn solve(input: &str) {
         }
     }
 
-    println!("Part One: {}", &hash1);
-
-    println!(
-        "Part Two: {}",
-        hash2.map(Option::unwrap).iter().collect::<String>()
-    );
+    vec![hash1, hash2.map(Option::unwrap).iter().collect::<String>()]
 }
 
 #[cfg(test)]
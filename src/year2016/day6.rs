@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-pub fn solve(input: &str) {
+pub fn solve(input: &str) -> Vec<String> {
     let mut counts: Vec<HashMap<char, u32>> = vec![HashMap::new(); 8];
 
     input.lines().for_each(|line| {
@@ -33,9 +33,7 @@ pub fn solve(input: &str) {
             },
         );
 
-    println!("Part One: {part1}");
-
-    println!("Part Two: {part2}");
+    vec![part1, part2]
 }
 
 #[cfg(test)]
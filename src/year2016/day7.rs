@@ -3,14 +3,14 @@ use nom::{
     sequence::delimited, IResult,
 };
 
-pub fn solve(input: &str) {
+pub fn solve(input: &str) -> Vec<String> {
     let lines: Vec<Vec<Piece>> = input.lines().flat_map(parse_line).map(|l| l.1).collect();
 
-    let valid = lines.iter().filter(|line| is_valid_part_1(line)).count();
-    println!("Part One: {valid}");
+    let part1 = lines.iter().filter(|line| is_valid_part_1(line)).count();
 
-    let valid = lines.iter().filter(|line| is_valid_part_2(line)).count();
-    println!("Part Two: {valid}");
+    let part2 = lines.iter().filter(|line| is_valid_part_2(line)).count();
+
+    vec![part1.to_string(), part2.to_string()]
 }
 
 #[derive(Debug, PartialEq, Eq)]
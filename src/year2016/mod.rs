@@ -0,0 +1,76 @@
+pub mod assembunny;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+use crate::Puzzle;
+
+pub const PUZZLES: &[Puzzle] = &[
+    Puzzle {
+        day: 1,
+        solve: day1::solve,
+        input: include_str!("../data/2016/day1.txt"),
+        answers: &[],
+    },
+    Puzzle {
+        day: 2,
+        solve: day2::solve,
+        input: include_str!("../data/2016/day2.txt"),
+        answers: &[],
+    },
+    Puzzle {
+        day: 3,
+        solve: day3::solve,
+        input: include_str!("../data/2016/day3.txt"),
+        answers: &[],
+    },
+    Puzzle {
+        day: 4,
+        solve: day4::solve,
+        input: include_str!("../data/2016/day4.txt"),
+        answers: &[],
+    },
+    Puzzle {
+        day: 5,
+        solve: day5::solve,
+        input: include_str!("../data/2016/day5.txt"),
+        answers: &[],
+    },
+    Puzzle {
+        day: 6,
+        solve: day6::solve,
+        input: include_str!("../data/2016/day6.txt"),
+        answers: &[],
+    },
+    Puzzle {
+        day: 7,
+        solve: day7::solve,
+        input: include_str!("../data/2016/day7.txt"),
+        answers: &[],
+    },
+    Puzzle {
+        day: 23,
+        solve: day23::solve,
+        input: include_str!("../data/2016/day23.txt"),
+        answers: &[],
+    },
+    Puzzle {
+        day: 24,
+        solve: day24::solve,
+        input: include_str!("../data/2016/day24.txt"),
+        answers: &[],
+    },
+    Puzzle {
+        day: 25,
+        solve: day25::solve,
+        input: include_str!("../data/2016/day25.txt"),
+        answers: &[],
+    },
+];